@@ -0,0 +1,828 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+    ops::RangeInclusive,
+};
+
+pub use ranges::{Id, InclusiveRange};
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    /// Not made of two IDs joined by '-'
+    InvalidRange {
+        raw: String,
+    },
+    InvalidId {
+        raw: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Unable to read the input: {}", error),
+            Self::InvalidRange { raw } => write!(f, "Invalid range {:?}", raw),
+            Self::InvalidId { raw } => write!(f, "Invalid ID {:?}", raw),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+pub struct ProductRangesParser {}
+
+impl ProductRangesParser {
+    pub fn parse(data: &str) -> impl Iterator<Item = Id> {
+        data.split(',')
+            .flat_map(|range| ProductRange::from(range).generate_ids())
+    }
+
+    /// Stream the IDs of the ranges read from `reader`, which are separated by commas or line
+    /// breaks. A range split between two reads is buffered until it's complete, and reading
+    /// stops after the first I/O error
+    pub fn parse_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Id, ParseError>> {
+        let mut is_failed = false;
+
+        reader
+            .split(b',')
+            .map_while(move |segment| {
+                (!is_failed).then(|| {
+                    is_failed = segment.is_err();
+                    segment
+                })
+            })
+            .flat_map(|segment| match segment {
+                Ok(segment) => String::from_utf8_lossy(&segment)
+                    .split_whitespace()
+                    .map(ProductRange::try_parse)
+                    .collect(),
+                Err(error) => Vec::from([Err(ParseError::Io(error))]),
+            })
+            .flat_map(|range| {
+                let (ids, error) = match range {
+                    Ok(range) => (Some(range.into_iter().map(Ok)), None),
+                    Err(error) => (None, Some(Err(error))),
+                };
+
+                ids.into_iter().flatten().chain(error)
+            })
+    }
+
+    /// Sorted and non-overlapping ranges, without expanding them into IDs
+    pub fn parse_optimized(data: &str) -> Vec<ProductRange> {
+        let ranges: Vec<ProductRange> = data.split(',').map(ProductRange::from).collect();
+
+        ProductRangesUtils::merge_ranges(&ranges)
+    }
+}
+
+pub struct ProductRangesUtils {}
+
+impl ProductRangesUtils {
+    /// Minimal sorted list of ranges covering the same IDs, merging overlapping and adjacent ones
+    pub fn merge_ranges(ranges: &[ProductRange]) -> Vec<ProductRange> {
+        let mut ranges = ranges.to_owned();
+        ranges.sort_by_key(|range| range.first_id);
+
+        let mut merged_ranges: Vec<ProductRange> = Vec::with_capacity(ranges.len());
+
+        for range in ranges {
+            match merged_ranges.pop() {
+                Some(last_range) => merged_ranges.extend(last_range.union(&range)),
+                None => merged_ranges.push(range),
+            }
+        }
+
+        merged_ranges
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProductRange {
+    first_id: Id,
+    last_id: Id,
+}
+
+impl ProductRange {
+    fn try_parse(value: &str) -> Result<Self, ParseError> {
+        let (first_id, last_id) =
+            value
+                .split_once('-')
+                .ok_or_else(|| ParseError::InvalidRange {
+                    raw: value.to_string(),
+                })?;
+
+        let parse_id = |raw: &str| {
+            raw.parse().map_err(|_| ParseError::InvalidId {
+                raw: raw.to_string(),
+            })
+        };
+
+        Ok(Self {
+            first_id: parse_id(first_id)?,
+            last_id: parse_id(last_id)?,
+        })
+    }
+
+    fn generate_ids(self) -> impl Iterator<Item = Id> {
+        self.into_iter()
+    }
+
+    /// Every `step`-th ID starting from the first one
+    pub fn generate_ids_with_step(&self, step: u64) -> impl Iterator<Item = Id> {
+        assert!(step > 0, "Step must be greater than zero");
+
+        // A step beyond usize can only yield the first ID anyway
+        self.into_iter()
+            .step_by(usize::try_from(step).unwrap_or(usize::MAX))
+    }
+
+    pub fn intersection(&self, other: &ProductRange) -> Option<ProductRange> {
+        let first_id = self.first_id.max(other.first_id);
+        let last_id = self.last_id.min(other.last_id);
+
+        (first_id <= last_id).then_some(ProductRange { first_id, last_id })
+    }
+
+    /// Merged range when both overlap or are adjacent, otherwise both ranges unchanged
+    pub fn union(&self, other: &ProductRange) -> Vec<ProductRange> {
+        let is_mergeable = self.first_id <= other.last_id.saturating_add(1)
+            && other.first_id <= self.last_id.saturating_add(1);
+
+        match is_mergeable {
+            true => Vec::from([ProductRange {
+                first_id: self.first_id.min(other.first_id),
+                last_id: self.last_id.max(other.last_id),
+            }]),
+            false => Vec::from([self.clone(), other.clone()]),
+        }
+    }
+
+    /// IDs of this range missing from the other one, as up to two ranges in ascending order
+    pub fn difference(&self, other: &ProductRange) -> Vec<ProductRange> {
+        let Some(overlap) = self.intersection(other) else {
+            return Vec::from([self.clone()]);
+        };
+
+        let mut ranges = Vec::new();
+
+        if self.first_id < overlap.first_id {
+            ranges.push(ProductRange {
+                first_id: self.first_id,
+                last_id: overlap.first_id - 1,
+            });
+        }
+
+        if overlap.last_id < self.last_id {
+            ranges.push(ProductRange {
+                first_id: overlap.last_id + 1,
+                last_id: self.last_id,
+            });
+        }
+
+        ranges
+    }
+}
+
+impl InclusiveRange for ProductRange {
+    fn lower(&self) -> Id {
+        self.first_id
+    }
+
+    fn upper(&self) -> Id {
+        self.last_id
+    }
+}
+
+impl IntoIterator for ProductRange {
+    type Item = Id;
+    type IntoIter = RangeInclusive<Id>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.first_id..=self.last_id
+    }
+}
+
+impl IntoIterator for &ProductRange {
+    type Item = Id;
+    type IntoIter = RangeInclusive<Id>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.first_id..=self.last_id
+    }
+}
+
+impl From<&str> for ProductRange {
+    fn from(value: &str) -> Self {
+        let mut ids = value.split('-').take(2);
+
+        let first_id = ids
+            .next()
+            .unwrap()
+            .parse()
+            .expect("Invalid first number in input");
+
+        let last_id = ids
+            .next()
+            .unwrap()
+            .parse()
+            .expect("Invalid second number in input");
+
+        Self { first_id, last_id }
+    }
+}
+
+pub trait IdValidation {
+    fn get_groups_sizes(id: &str) -> impl Iterator<Item = usize>;
+
+    /// Size of the first group which is repeated along the whole ID
+    fn find_repeated_group_size(id: &str) -> Option<usize> {
+        // Pattern must be repeated at least twice
+        if id.len() == 1 {
+            return None;
+        }
+
+        Self::get_groups_sizes(id).find(|&group_size| {
+            let mut grouped_data = id.as_bytes().chunks(group_size);
+
+            let first_item = grouped_data.next().unwrap();
+            grouped_data.all(|group| group == first_item)
+        })
+    }
+
+    fn is_valid(id: &Id) -> bool {
+        Self::find_repeated_group_size(&id.to_string()).is_none()
+    }
+
+    fn explain_invalidity(id: &Id) -> Option<String> {
+        let id = id.to_string();
+        let group_size = Self::find_repeated_group_size(&id)?;
+
+        Some(format!(
+            "ID {} repeats the pattern {} (group size {}) {} times",
+            id,
+            &id[..group_size],
+            group_size,
+            id.len() / group_size
+        ))
+    }
+
+    fn find_invalid_ids(ids: &[Id]) -> impl Iterator<Item = &Id> {
+        ids.iter().filter(|&id| !Self::is_valid(id))
+    }
+}
+
+pub struct BasicIdValidator {}
+
+impl IdValidation for BasicIdValidator {
+    fn get_groups_sizes(id: &str) -> impl Iterator<Item = usize> {
+        [(id.len() as f64 / 2.)]
+            .into_iter()
+            .filter(|size| size.fract() == 0.0)
+            .map(|size| size as usize)
+    }
+}
+
+pub struct StrictIdValidator {}
+
+impl IdValidation for StrictIdValidator {
+    fn get_groups_sizes(id: &str) -> impl Iterator<Item = usize> {
+        let max_size = id.len() / 2;
+
+        (1..=max_size).filter(|&size| id.len().is_multiple_of(size)) // TODO: how? move isn't taking ownership of id?
+    }
+}
+
+type Validator = Box<dyn Fn(&Id) -> bool>;
+
+/// Validators looked up by name at runtime, e.g. when chosen from a CLI flag
+#[derive(Default)]
+pub struct ValidatorRegistry {
+    validators: HashMap<String, Validator>,
+}
+
+impl ValidatorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces any validator already registered under the same name
+    pub fn register<V: IdValidation>(&mut self, name: &str) {
+        self.validators
+            .insert(name.to_string(), Box::new(|id: &Id| V::is_valid(id)));
+    }
+
+    pub fn validate(&self, name: &str, id: &Id) -> Option<bool> {
+        self.validators.get(name).map(|validator| validator(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Cursor, Read};
+
+    use super::*;
+
+    const PRODUCT_RANGE: ProductRange = ProductRange {
+        first_id: 95,
+        last_id: 115,
+    };
+
+    #[test]
+    fn parse_product_range() {
+        let product_range_raw = "95-115";
+        let product_range = ProductRange::from(product_range_raw);
+
+        assert_eq!(product_range.first_id, 95);
+        assert_eq!(product_range.last_id, 115);
+    }
+
+    #[test]
+    fn generate_ids() {
+        let ids: Vec<Id> = PRODUCT_RANGE.generate_ids().collect();
+
+        assert_eq!(ids.len(), 21);
+        assert_eq!(*ids.first().unwrap(), 95);
+        assert_eq!(*ids.get(1).unwrap(), 96);
+        assert_eq!(*ids.iter().nth_back(1).unwrap(), 114);
+        assert_eq!(*ids.last().unwrap(), 115);
+    }
+
+    #[test]
+    fn product_range_as_inclusive_range() {
+        assert_eq!((PRODUCT_RANGE.lower(), PRODUCT_RANGE.upper()), (95, 115));
+        assert_eq!(PRODUCT_RANGE.size(), 21);
+        assert!(PRODUCT_RANGE.contains(95) && PRODUCT_RANGE.contains(115));
+        assert!(!PRODUCT_RANGE.contains(94) && !PRODUCT_RANGE.contains(116));
+        assert_eq!(
+            PRODUCT_RANGE.iter_ids().collect::<Vec<Id>>(),
+            PRODUCT_RANGE.generate_ids().collect::<Vec<Id>>()
+        );
+    }
+
+    #[test]
+    fn generate_ids_with_step() {
+        let product_range = ProductRange {
+            first_id: 0,
+            last_id: 10,
+        };
+
+        let ids_with_step =
+            |step| -> Vec<Id> { product_range.generate_ids_with_step(step).collect() };
+
+        assert_eq!(ids_with_step(3), Vec::from([0, 3, 6, 9]));
+        assert_eq!(ids_with_step(2), Vec::from([0, 2, 4, 6, 8, 10]));
+        assert_eq!(ids_with_step(5), Vec::from([0, 5, 10]));
+        assert_eq!(ids_with_step(11), Vec::from([0]));
+        assert_eq!(ids_with_step(100), Vec::from([0]));
+        assert_eq!(ids_with_step(10), Vec::from([0, 10]));
+        assert_eq!(
+            PRODUCT_RANGE.generate_ids_with_step(1).collect::<Vec<Id>>(),
+            PRODUCT_RANGE.generate_ids().collect::<Vec<Id>>()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Step must be greater than zero")]
+    fn generate_ids_with_zero_step() {
+        let _ = PRODUCT_RANGE.generate_ids_with_step(0);
+    }
+
+    #[test]
+    fn parse_from_reader() {
+        let ids = |reader: &mut dyn BufRead| -> Vec<Id> {
+            ProductRangesParser::parse_from_reader(reader)
+                .collect::<Result<Vec<Id>, ParseError>>()
+                .unwrap()
+        };
+
+        let data = "11-22,95-115,998-1012\n";
+        let expected_ids: Vec<Id> = ProductRangesParser::parse(data.trim()).collect();
+
+        assert_eq!(ids(&mut Cursor::new(data.as_bytes())), expected_ids);
+
+        // Every range is split between several reads
+        assert_eq!(
+            ids(&mut BufReader::with_capacity(
+                2,
+                Cursor::new(data.as_bytes())
+            )),
+            expected_ids
+        );
+
+        // Line breaks also separate ranges, and empty ones are skipped
+        assert_eq!(
+            ids(&mut Cursor::new(b"11-22,\n95-115\n998-1012,".as_slice())),
+            expected_ids
+        );
+        assert!(ids(&mut Cursor::new(b"".as_slice())).is_empty());
+    }
+
+    #[test]
+    fn parse_from_reader_errors() {
+        let results: Vec<Result<Id, ParseError>> =
+            ProductRangesParser::parse_from_reader(Cursor::new(b"1-2,x-5,7,3-3".as_slice()))
+                .collect();
+
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0].as_ref().unwrap(), &1);
+        assert_eq!(results[1].as_ref().unwrap(), &2);
+        assert!(matches!(&results[2], Err(ParseError::InvalidId { raw }) if raw == "x"));
+        assert!(matches!(&results[3], Err(ParseError::InvalidRange { raw }) if raw == "7"));
+        assert_eq!(results[4].as_ref().unwrap(), &3);
+
+        struct FailingReader;
+
+        impl Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk on fire"))
+            }
+        }
+
+        let results: Vec<Result<Id, ParseError>> =
+            ProductRangesParser::parse_from_reader(BufReader::new(FailingReader)).collect();
+
+        // A single error instead of retrying forever
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].as_ref().unwrap_err().to_string(),
+            "Unable to read the input: disk on fire"
+        );
+    }
+
+    #[test]
+    fn iterate_product_range() {
+        let mut ids = Vec::new();
+
+        for id in &PRODUCT_RANGE {
+            ids.push(id);
+        }
+
+        let expected_ids: Vec<Id> = PRODUCT_RANGE.generate_ids().collect();
+
+        assert_eq!(ids, expected_ids);
+        assert_eq!(PRODUCT_RANGE.into_iter().collect::<Vec<Id>>(), expected_ids);
+        assert_eq!(
+            (&PRODUCT_RANGE)
+                .into_iter()
+                .filter(|id| id % 10 == 0)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn get_groups_sizes() {
+        let id = "12341234";
+
+        let groups_sizes_basic: Vec<usize> = BasicIdValidator::get_groups_sizes(id).collect();
+
+        assert_eq!(groups_sizes_basic, vec![4]);
+
+        let groups_sizes_strict: Vec<usize> = StrictIdValidator::get_groups_sizes(id).collect();
+
+        assert_eq!(groups_sizes_strict.len(), 3);
+        assert!(groups_sizes_strict.contains(&1));
+        assert!(groups_sizes_strict.contains(&2));
+        assert!(groups_sizes_strict.contains(&4));
+
+        let id = "222222222";
+
+        let groups_sizes_basic: Vec<usize> = BasicIdValidator::get_groups_sizes(id).collect();
+
+        assert!(groups_sizes_basic.is_empty());
+
+        let groups_sizes_strict: Vec<usize> = StrictIdValidator::get_groups_sizes(id).collect();
+
+        assert_eq!(groups_sizes_strict.len(), 2);
+        assert!(groups_sizes_strict.contains(&1));
+        assert!(groups_sizes_strict.contains(&3));
+    }
+
+    #[test]
+    fn check_id_simple() {
+        assert!(BasicIdValidator::is_valid(&PRODUCT_RANGE.first_id));
+        assert!(BasicIdValidator::is_valid(&PRODUCT_RANGE.last_id));
+
+        assert!(!BasicIdValidator::is_valid(&99));
+        assert!(BasicIdValidator::is_valid(&111));
+    }
+
+    #[test]
+    fn find_invalid_ids_simple() {
+        let product_range = ProductRange {
+            first_id: 11,
+            last_id: 22,
+        };
+
+        let ids: Vec<Id> = product_range.generate_ids().collect();
+
+        let invalid_ids: Vec<&Id> = BasicIdValidator::find_invalid_ids(&ids).collect();
+
+        assert_eq!(invalid_ids, Vec::from([&11, &22]));
+    }
+
+    #[test]
+    fn check_id_strict() {
+        assert!(StrictIdValidator::is_valid(&PRODUCT_RANGE.first_id));
+        assert!(StrictIdValidator::is_valid(&PRODUCT_RANGE.last_id));
+
+        assert!(!StrictIdValidator::is_valid(&99));
+        assert!(!StrictIdValidator::is_valid(&111));
+
+        assert!(!StrictIdValidator::is_valid(&565656));
+        assert!(!StrictIdValidator::is_valid(&446446));
+    }
+
+    #[test]
+    fn find_invalid_ids_strict() {
+        let ids: Vec<Id> = PRODUCT_RANGE.generate_ids().collect();
+
+        let invalid_ids: Vec<&Id> = StrictIdValidator::find_invalid_ids(&ids).collect();
+
+        assert_eq!(invalid_ids, Vec::from([&99, &111]));
+    }
+
+    #[test]
+    fn check_example_input() {
+        let example_input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
+        let products_ids: Vec<Id> = ProductRangesParser::parse(example_input).collect();
+
+        let invalid_ids_basic = BasicIdValidator::find_invalid_ids(&products_ids);
+        assert_eq!(invalid_ids_basic.sum::<u64>(), 1227775554);
+
+        let invalid_ids_strict = StrictIdValidator::find_invalid_ids(&products_ids);
+        assert_eq!(invalid_ids_strict.sum::<u64>(), 4174379265);
+    }
+
+    #[test]
+    fn parse_optimized_ranges() {
+        let data = "95-115,10-20,110-130,21-25,200-210";
+
+        let ranges = ProductRangesParser::parse_optimized(data);
+
+        assert_eq!(
+            ranges,
+            Vec::from([
+                ProductRange {
+                    first_id: 10,
+                    last_id: 25
+                },
+                ProductRange {
+                    first_id: 95,
+                    last_id: 130
+                },
+                ProductRange {
+                    first_id: 200,
+                    last_id: 210
+                }
+            ])
+        );
+
+        let mut ids: Vec<Id> = ProductRangesParser::parse(data).collect();
+        ids.sort();
+        ids.dedup();
+
+        assert_eq!(
+            ranges.iter().map(|range| range.size()).sum::<u128>(),
+            ids.len() as u128
+        );
+        assert_eq!(ranges.iter().flatten().collect::<Vec<Id>>(), ids);
+
+        let example_input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224";
+
+        assert_eq!(ProductRangesParser::parse_optimized(example_input).len(), 5);
+        assert!(ProductRangesUtils::merge_ranges(&[]).is_empty());
+    }
+
+    #[test]
+    fn union_ranges() {
+        let overlapping_range = ProductRange {
+            first_id: 110,
+            last_id: 130,
+        };
+
+        assert_eq!(
+            PRODUCT_RANGE.union(&overlapping_range),
+            Vec::from([ProductRange {
+                first_id: 95,
+                last_id: 130
+            }])
+        );
+
+        let adjacent_range = ProductRange {
+            first_id: 116,
+            last_id: 120,
+        };
+
+        assert_eq!(
+            PRODUCT_RANGE.union(&adjacent_range),
+            Vec::from([ProductRange {
+                first_id: 95,
+                last_id: 120
+            }])
+        );
+
+        let disjoint_range = ProductRange {
+            first_id: 117,
+            last_id: 120,
+        };
+
+        assert_eq!(
+            PRODUCT_RANGE.union(&disjoint_range),
+            Vec::from([PRODUCT_RANGE, disjoint_range])
+        );
+    }
+
+    #[test]
+    fn intersect_ranges() {
+        let overlapping_range = ProductRange {
+            first_id: 110,
+            last_id: 130,
+        };
+
+        assert_eq!(
+            PRODUCT_RANGE.intersection(&overlapping_range),
+            Some(ProductRange {
+                first_id: 110,
+                last_id: 115
+            })
+        );
+
+        let disjoint_range = ProductRange {
+            first_id: 116,
+            last_id: 120,
+        };
+
+        assert_eq!(PRODUCT_RANGE.intersection(&disjoint_range), None);
+    }
+
+    #[test]
+    fn difference_ranges() {
+        let disjoint_range = ProductRange {
+            first_id: 116,
+            last_id: 120,
+        };
+
+        assert_eq!(
+            PRODUCT_RANGE.difference(&disjoint_range),
+            Vec::from([PRODUCT_RANGE])
+        );
+
+        let contained_range = ProductRange {
+            first_id: 100,
+            last_id: 105,
+        };
+
+        assert_eq!(
+            PRODUCT_RANGE.difference(&contained_range),
+            Vec::from([
+                ProductRange {
+                    first_id: 95,
+                    last_id: 99
+                },
+                ProductRange {
+                    first_id: 106,
+                    last_id: 115
+                }
+            ])
+        );
+
+        let overlapping_range = ProductRange {
+            first_id: 110,
+            last_id: 130,
+        };
+
+        assert_eq!(
+            PRODUCT_RANGE.difference(&overlapping_range),
+            Vec::from([ProductRange {
+                first_id: 95,
+                last_id: 109
+            }])
+        );
+        assert_eq!(
+            overlapping_range.difference(&PRODUCT_RANGE),
+            Vec::from([ProductRange {
+                first_id: 116,
+                last_id: 130
+            }])
+        );
+
+        let covering_range = ProductRange {
+            first_id: 90,
+            last_id: 120,
+        };
+
+        assert!(PRODUCT_RANGE.difference(&covering_range).is_empty());
+        assert!(PRODUCT_RANGE.difference(&PRODUCT_RANGE).is_empty());
+
+        // Splitting the range between both operations keeps every ID
+        for other_range in [disjoint_range, contained_range, overlapping_range] {
+            let difference_size: u128 = PRODUCT_RANGE
+                .difference(&other_range)
+                .iter()
+                .map(|range| range.size())
+                .sum();
+
+            let intersection_size = PRODUCT_RANGE
+                .intersection(&other_range)
+                .map_or(0, |range| range.size());
+
+            assert_eq!(difference_size + intersection_size, PRODUCT_RANGE.size());
+        }
+    }
+
+    #[test]
+    fn union_and_intersection_sizes() {
+        let ranges = [
+            ProductRange {
+                first_id: 110,
+                last_id: 130,
+            },
+            ProductRange {
+                first_id: 116,
+                last_id: 120,
+            },
+            ProductRange {
+                first_id: 100,
+                last_id: 105,
+            },
+        ];
+
+        for other_range in ranges.iter() {
+            let union_size: u128 = PRODUCT_RANGE
+                .union(other_range)
+                .iter()
+                .map(|range| range.size())
+                .sum();
+
+            let intersection_size = PRODUCT_RANGE
+                .intersection(other_range)
+                .map_or(0, |range| range.size());
+
+            assert_eq!(
+                union_size + intersection_size,
+                PRODUCT_RANGE.size() + other_range.size()
+            );
+        }
+    }
+
+    #[test]
+    fn explain_invalidity() {
+        assert_eq!(
+            BasicIdValidator::explain_invalidity(&446446),
+            Some(String::from(
+                "ID 446446 repeats the pattern 446 (group size 3) 2 times"
+            ))
+        );
+        assert_eq!(BasicIdValidator::explain_invalidity(&111), None);
+        assert_eq!(BasicIdValidator::explain_invalidity(&95), None);
+
+        assert_eq!(
+            StrictIdValidator::explain_invalidity(&565656),
+            Some(String::from(
+                "ID 565656 repeats the pattern 56 (group size 2) 3 times"
+            ))
+        );
+        assert!(
+            !StrictIdValidator::explain_invalidity(&111)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(StrictIdValidator::explain_invalidity(&115), None);
+        assert_eq!(StrictIdValidator::explain_invalidity(&7), None);
+    }
+
+    #[test]
+    fn validator_registry() {
+        let mut validator_registry = ValidatorRegistry::new();
+
+        validator_registry.register::<BasicIdValidator>("basic");
+        validator_registry.register::<StrictIdValidator>("strict");
+
+        assert_eq!(validator_registry.validate("basic", &1212), Some(false));
+        assert_eq!(validator_registry.validate("strict", &1212), Some(false));
+
+        assert_eq!(validator_registry.validate("basic", &121212), Some(true));
+        assert_eq!(validator_registry.validate("strict", &121212), Some(false));
+
+        assert_eq!(validator_registry.validate("basic", &1234), Some(true));
+        assert_eq!(validator_registry.validate("unknown", &1234), None);
+
+        // Registering again under the same name replaces the validator
+        validator_registry.register::<BasicIdValidator>("strict");
+
+        assert_eq!(validator_registry.validate("strict", &121212), Some(true));
+    }
+}
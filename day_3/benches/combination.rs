@@ -0,0 +1,62 @@
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use day_3::{
+    BatteryBank,
+    generator::{Distribution, random_bank_with},
+};
+
+fn max_joltage_combination(c: &mut Criterion) {
+    let mut group = c.benchmark_group("max_joltage_combination");
+
+    let distributions = [
+        ("uniform", Distribution::Uniform),
+        ("heavy", Distribution::Heavy(9)),
+        ("decreasing", Distribution::Decreasing),
+    ];
+
+    for (name, distribution) in distributions {
+        let battery_bank_raw = random_bank_with(10_000, 42, distribution);
+
+        // Up to 19 digits, the longest combination fitting in a joltage
+        for combination_size in [2, 12, 19] {
+            group.bench_with_input(
+                BenchmarkId::new(name, combination_size),
+                &combination_size,
+                |b, &combination_size| {
+                    // Build a fresh bank on each iteration so the cache doesn't hide the work
+                    b.iter_batched(
+                        || BatteryBank::from(battery_bank_raw.as_str()),
+                        |battery_bank| battery_bank.get_max_joltage_combination(combination_size),
+                        criterion::BatchSize::SmallInput,
+                    )
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+fn cached_max_joltage_combination(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cached_max_joltage_combination");
+
+    let battery_bank =
+        BatteryBank::from(random_bank_with(10_000, 42, Distribution::Uniform).as_str());
+
+    // The same bank queried with several sizes, every query after the first one is a cache hit
+    group.bench_function("sizes_2_to_19", |b| {
+        b.iter(|| {
+            (2..=19)
+                .map(|combination_size| battery_bank.get_max_joltage_combination(combination_size))
+                .max()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    max_joltage_combination,
+    cached_max_joltage_combination
+);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+use std::{
+    hint::black_box,
+    io::{BufRead, Cursor},
+};
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use day_3::{BatteryBank, generator::random_banks};
+
+fn parse_banks(c: &mut Criterion) {
+    // About 100 MB of synthetic input
+    let data = random_banks(1_000_000, 100, 42);
+
+    let mut group = c.benchmark_group("parse_banks");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(data.len() as u64));
+
+    group.bench_function("lines_str", |b| {
+        b.iter(|| {
+            for line in Cursor::new(data.as_bytes()).lines() {
+                black_box(BatteryBank::from(line.unwrap().as_str()));
+            }
+        })
+    });
+
+    group.bench_function("read_until_bytes", |b| {
+        b.iter(|| {
+            let mut reader = Cursor::new(data.as_bytes());
+            let mut line = Vec::new();
+
+            while reader.read_until(b'\n', &mut line).unwrap() > 0 {
+                black_box(BatteryBank::try_from(line.trim_ascii_end()).unwrap());
+                line.clear();
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parse_banks);
+criterion_main!(benches);
@@ -0,0 +1,101 @@
+use std::{collections::HashMap, error::Error, fmt};
+
+use crate::Joltage;
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum AlphabetError {
+    DuplicateSymbol(char),
+    Empty,
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateSymbol(symbol) => write!(f, "Duplicate symbol {:?} in alphabet", symbol),
+            Self::Empty => write!(f, "Alphabet without symbols"),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
+
+/// Mapping from the symbols used in the input to their joltage
+#[derive(Debug)]
+pub struct DigitAlphabet {
+    symbols: HashMap<char, Joltage>,
+    base: Joltage,
+}
+
+impl DigitAlphabet {
+    pub fn new(symbols: impl IntoIterator<Item = (char, Joltage)>) -> Result<Self, AlphabetError> {
+        let mut symbols_map = HashMap::new();
+
+        for (symbol, joltage) in symbols {
+            if symbols_map.insert(symbol, joltage).is_some() {
+                return Err(AlphabetError::DuplicateSymbol(symbol));
+            }
+        }
+
+        // Big enough to hold any joltage as a single digit
+        let base = symbols_map.values().max().ok_or(AlphabetError::Empty)? + 1;
+
+        Ok(Self {
+            symbols: symbols_map,
+            base: base.max(2),
+        })
+    }
+
+    pub fn joltage(&self, symbol: char) -> Option<Joltage> {
+        self.symbols.get(&symbol).copied()
+    }
+
+    /// Distinct joltages of the alphabet, in ascending order
+    pub fn joltages(&self) -> Vec<Joltage> {
+        let mut joltages: Vec<Joltage> = self.symbols.values().copied().collect();
+
+        joltages.sort_unstable();
+        joltages.dedup();
+
+        joltages
+    }
+
+    /// Base used to concatenate the joltages of a combination
+    pub fn base(&self) -> Joltage {
+        self.base
+    }
+}
+
+impl Default for DigitAlphabet {
+    /// Standard decimal digits, where batteries go from 1 to 9. '0' is left out on purpose,
+    /// as no battery has a null joltage, so it accepts the same banks as the byte parser
+    fn default() -> Self {
+        Self::new(('1'..='9').zip(1..)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_alphabet() {
+        let alphabet = DigitAlphabet::default();
+
+        assert_eq!(alphabet.base(), 10);
+        assert_eq!(alphabet.joltage('1'), Some(1));
+        assert_eq!(alphabet.joltage('9'), Some(9));
+        assert_eq!(alphabet.joltage('a'), None);
+        assert_eq!(alphabet.joltage('0'), None);
+        assert_eq!(alphabet.joltages(), Vec::from([1, 2, 3, 4, 5, 6, 7, 8, 9]));
+    }
+
+    #[test]
+    fn invalid_alphabets() {
+        assert_eq!(
+            DigitAlphabet::new([('a', 1), ('b', 2), ('a', 3)]).err(),
+            Some(AlphabetError::DuplicateSymbol('a'))
+        );
+
+        assert_eq!(DigitAlphabet::new([]).err(), Some(AlphabetError::Empty));
+    }
+}
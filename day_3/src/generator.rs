@@ -0,0 +1,150 @@
+//! Seeded battery banks generator for tests and benchmarks.
+//!
+//! Uses a xorshift64 generator so the same seed produces the same banks on
+//! every platform.
+
+/// Shape of the digits in the generated banks
+#[derive(Clone, Copy, Debug)]
+pub enum Distribution {
+    /// Every digit between 1-9 is equally likely
+    Uniform,
+    /// Roughly three out of four batteries use the given digit
+    Heavy(u8),
+    /// Non-increasing digits, in runs of the same length from 9 down to 1, the greedy's worst
+    /// case. Banks shorter than 9 skip some digits instead, descending strictly
+    Decreasing,
+}
+
+struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // A zero state would only ever produce zeros
+        let state = match seed {
+            0 => 0x9E37_79B9_7F4A_7C15,
+            seed => seed,
+        };
+
+        Self { state }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        self.state
+    }
+
+    fn next_digit(&mut self) -> u8 {
+        (self.next() % 9) as u8 + 1
+    }
+}
+
+fn generate_bank(rng: &mut XorShift, len: usize, distribution: Distribution) -> String {
+    match distribution {
+        Distribution::Uniform => (0..len).map(|_| rng.next_digit()).map(to_char).collect(),
+        Distribution::Heavy(digit) => {
+            assert!((1..=9).contains(&digit), "Heavy digit must be between 1-9");
+
+            (0..len)
+                .map(|_| match rng.next() % 4 {
+                    0 => rng.next_digit(),
+                    _ => digit,
+                })
+                .map(to_char)
+                .collect()
+        }
+        // Doesn't need any randomness, every bank of a given length is the same
+        Distribution::Decreasing => (0..len)
+            .map(|index| 9 - (index * 9 / len) as u8)
+            .map(to_char)
+            .collect(),
+    }
+}
+
+fn to_char(digit: u8) -> char {
+    (b'0' + digit) as char
+}
+
+pub fn random_bank(len: usize, seed: u64) -> String {
+    random_bank_with(len, seed, Distribution::Uniform)
+}
+
+pub fn random_bank_with(len: usize, seed: u64, distribution: Distribution) -> String {
+    generate_bank(&mut XorShift::new(seed), len, distribution)
+}
+
+/// Generate `n` banks separated by newlines, as in the puzzle input
+pub fn random_banks(n: usize, len: usize, seed: u64) -> String {
+    random_banks_with(n, len, seed, Distribution::Uniform)
+}
+
+pub fn random_banks_with(n: usize, len: usize, seed: u64, distribution: Distribution) -> String {
+    let mut rng = XorShift::new(seed);
+
+    (0..n)
+        .map(|_| generate_bank(&mut rng, len, distribution))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_for_fixed_seed() {
+        assert_eq!(random_bank(15, 42), random_bank(15, 42));
+        assert_eq!(random_banks(10, 40, 7), random_banks(10, 40, 7));
+
+        assert_ne!(random_bank(15, 42), random_bank(15, 43));
+
+        // Pin the output so any change in the generator is noticed
+        assert_eq!(random_bank(15, 42), "824514244277862");
+    }
+
+    #[test]
+    fn generated_banks_shape() {
+        let banks = random_banks(5, 20, 1);
+
+        assert_eq!(banks.lines().count(), 5);
+        assert!(banks.lines().all(|bank| bank.len() == 20));
+        assert!(
+            banks
+                .lines()
+                .flat_map(|bank| bank.chars())
+                .all(|char| ('1'..='9').contains(&char))
+        );
+    }
+
+    #[test]
+    fn heavy_distribution() {
+        let bank = random_bank_with(1000, 3, Distribution::Heavy(7));
+
+        let sevens = bank.chars().filter(|&char| char == '7').count();
+        assert!(sevens > 700);
+    }
+
+    #[test]
+    fn decreasing_distribution() {
+        let bank = random_bank_with(20, 5, Distribution::Decreasing);
+
+        let digits: Vec<u32> = bank
+            .chars()
+            .map(|char| char.to_digit(10).unwrap())
+            .collect();
+
+        assert!(digits.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!((digits[0], digits[19]), (9, 1));
+        assert!((1..=9).all(|digit| digits.contains(&digit)));
+
+        assert_eq!(
+            random_bank_with(27, 5, Distribution::Decreasing),
+            "999888777666555444333222111"
+        );
+        assert_eq!(random_bank_with(4, 5, Distribution::Decreasing), "9753");
+    }
+}
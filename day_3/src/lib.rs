@@ -0,0 +1,1819 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+    iter,
+};
+
+pub mod alphabet;
+#[cfg(any(test, feature = "gen"))]
+pub mod generator;
+
+use alphabet::DigitAlphabet;
+
+pub type Joltage = u64;
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum ParseError {
+    InvalidDigit {
+        byte: u8,
+        index: usize,
+    },
+    UnknownSymbol {
+        symbol: char,
+        index: usize,
+    },
+    /// Fewer batteries than the combinations to pick from the bank
+    TooShort {
+        len: usize,
+        min_len: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDigit { byte, index } => write!(
+                f,
+                "Invalid digit between 1-9: byte {:#04x} at index {}",
+                byte, index
+            ),
+            Self::UnknownSymbol { symbol, index } => {
+                write!(f, "Unknown symbol {:?} at index {}", symbol, index)
+            }
+            Self::TooShort { len, min_len } => write!(
+                f,
+                "Bank of {} batteries, at least {} are needed",
+                len, min_len
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct LineError {
+    pub line_number: usize,
+    pub raw: String,
+    pub error: ParseError,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Line {} ({:?}): {}",
+            self.line_number, self.raw, self.error
+        )
+    }
+}
+
+impl Error for LineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum SelectionError {
+    NotEnoughBatteries {
+        available: usize,
+        requested: usize,
+    },
+    /// The combination has too many digits for a joltage
+    Overflow {
+        requested: usize,
+    },
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotEnoughBatteries {
+                available,
+                requested,
+            } => write!(
+                f,
+                "Not enough batteries for the combination size: {} available, {} requested",
+                available, requested
+            ),
+            Self::Overflow { requested } => write!(
+                f,
+                "Combination of {} batteries doesn't fit in a joltage",
+                requested
+            ),
+        }
+    }
+}
+
+impl Error for SelectionError {}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Day3Error {
+    Parse(LineError),
+    Selection {
+        line_number: usize,
+        error: SelectionError,
+    },
+    Io {
+        line_number: usize,
+        kind: io::ErrorKind,
+    },
+}
+
+impl fmt::Display for Day3Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(error) => write!(f, "{}", error),
+            Self::Selection { line_number, error } => write!(f, "Line {}: {}", line_number, error),
+            Self::Io { line_number, kind } => {
+                write!(f, "Line {}: Unable to read input: {}", line_number, kind)
+            }
+        }
+    }
+}
+
+impl Error for Day3Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Parse(error) => Some(error),
+            Self::Selection { error, .. } => Some(error),
+            Self::Io { .. } => None,
+        }
+    }
+}
+
+impl From<LineError> for Day3Error {
+    fn from(value: LineError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+/// Non-empty banks of a line, which can be separated by commas
+fn line_banks(line: &[u8]) -> impl Iterator<Item = &[u8]> {
+    line.split(|&byte| byte == b',')
+        .map(|bank| bank.trim_ascii())
+        .filter(|bank| !bank.is_empty())
+}
+
+/// Non-empty banks of the input along with their 1-based line number
+fn banks_lines(data: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    data.split(|&byte| byte == b'\n')
+        .enumerate()
+        .flat_map(|(index, line)| line_banks(line).map(move |bank| (index + 1, bank)))
+}
+
+fn parse_bank_line(line_number: usize, line: &[u8]) -> Result<BatteryBank, LineError> {
+    BatteryBank::try_from(line).map_err(|error| LineError {
+        line_number,
+        raw: String::from_utf8_lossy(line).into_owned(),
+        error,
+    })
+}
+
+/// Parse every bank of the input, failing on the first invalid line
+pub fn parse_banks(data: &[u8]) -> Result<Vec<BatteryBank>, LineError> {
+    banks_lines(data)
+        .map(|(line_number, line)| parse_bank_line(line_number, line))
+        .collect()
+}
+
+/// Same as `parse_banks`, keeping the 1-based line number of every bank
+pub fn parse_numbered_banks(data: &[u8]) -> Result<Vec<(usize, BatteryBank)>, LineError> {
+    banks_lines(data)
+        .map(|(line_number, line)| {
+            parse_bank_line(line_number, line).map(|battery_bank| (line_number, battery_bank))
+        })
+        .collect()
+}
+
+/// Parse every bank of the input, collecting the invalid lines instead of failing. Banks with
+/// less than `min_len` batteries are invalid too, so picking combinations of up to `min_len`
+/// batteries can't fail afterwards. Every bank keeps its 1-based line number, as skipped lines
+/// leave gaps
+pub fn parse_banks_lenient(
+    data: &[u8],
+    min_len: usize,
+) -> (Vec<(usize, BatteryBank)>, Vec<LineError>) {
+    let mut batteries_banks = Vec::new();
+    let mut errors = Vec::new();
+
+    for (line_number, line) in banks_lines(data) {
+        let battery_bank = parse_bank_line(line_number, line).and_then(|battery_bank| {
+            match battery_bank.batteries.len() >= min_len {
+                true => Ok(battery_bank),
+                false => Err(LineError {
+                    line_number,
+                    raw: String::from_utf8_lossy(line).into_owned(),
+                    error: ParseError::TooShort {
+                        len: battery_bank.batteries.len(),
+                        min_len,
+                    },
+                }),
+            }
+        });
+
+        match battery_bank {
+            Ok(battery_bank) => batteries_banks.push((line_number, battery_bank)),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    (batteries_banks, errors)
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct BankResult {
+    pub line_number: usize,
+    /// Max joltage combination for each of the requested sizes, in the same order
+    pub values: Vec<Joltage>,
+}
+
+fn solve_bank_line(
+    line_number: usize,
+    line: &[u8],
+    sizes: &[usize],
+) -> Result<BankResult, Day3Error> {
+    let battery_bank = parse_bank_line(line_number, line)?;
+
+    let values = sizes
+        .iter()
+        .map(|&combination_size| {
+            battery_bank
+                .try_get_max_joltage_combination(combination_size)
+                .map_err(|error| Day3Error::Selection { line_number, error })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(BankResult {
+        line_number,
+        values,
+    })
+}
+
+/// Lazily parse and solve the banks line by line, without keeping them in memory
+pub fn results_iter(
+    mut reader: impl BufRead,
+    sizes: &[usize],
+) -> impl Iterator<Item = Result<BankResult, Day3Error>> {
+    let mut line = Vec::new();
+    let mut line_number = 0;
+    let mut bank_start = 0;
+    let mut is_finished = false;
+
+    iter::from_fn(move || {
+        loop {
+            // Pending banks of the current line
+            while bank_start < line.len() {
+                let bank_end = line[bank_start..]
+                    .iter()
+                    .position(|&byte| byte == b',')
+                    .map_or(line.len(), |position| bank_start + position);
+
+                let bank = line[bank_start..bank_end].trim_ascii();
+                bank_start = bank_end + 1;
+
+                if !bank.is_empty() {
+                    return Some(solve_bank_line(line_number, bank, sizes));
+                }
+            }
+
+            if is_finished {
+                return None;
+            }
+
+            line.clear();
+            line_number += 1;
+            bank_start = 0;
+
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => is_finished = true,
+                Ok(_) => {}
+                Err(error) => {
+                    is_finished = true;
+
+                    return Some(Err(Day3Error::Io {
+                        line_number,
+                        kind: error.kind(),
+                    }));
+                }
+            }
+        }
+    })
+}
+
+fn solve(data: &str, combination_size: usize) -> Result<Joltage, Day3Error> {
+    results_iter(data.as_bytes(), &[combination_size])
+        .map(|result| result.map(|bank_result| bank_result.values[0]))
+        .sum()
+}
+
+#[derive(Clone, Debug)]
+pub struct BankRef<'a> {
+    /// 1-based line of the bank in the input
+    pub line_number: usize,
+    pub bank: &'a BatteryBank,
+    pub combination: Joltage,
+}
+
+/// Banks with the largest and smallest combination among the banks numbered by their line,
+/// ties are resolved to the earliest bank
+pub fn best_and_worst_banks(
+    numbered_banks: &[(usize, BatteryBank)],
+    combination_size: usize,
+) -> Option<(BankRef<'_>, BankRef<'_>)> {
+    let mut banks_refs = numbered_banks.iter().map(|(line_number, bank)| BankRef {
+        line_number: *line_number,
+        bank,
+        combination: bank.get_max_joltage_combination(combination_size),
+    });
+
+    let first_bank = banks_refs.next()?;
+    let (mut best_bank, mut worst_bank) = (first_bank.clone(), first_bank);
+
+    for bank_ref in banks_refs {
+        if bank_ref.combination > best_bank.combination {
+            best_bank = bank_ref;
+        } else if bank_ref.combination < worst_bank.combination {
+            worst_bank = bank_ref;
+        }
+    }
+
+    Some((best_bank, worst_bank))
+}
+
+pub fn solve_part1(data: &str) -> Result<Joltage, Day3Error> {
+    solve(data, 2)
+}
+
+pub fn solve_part2(data: &str) -> Result<Joltage, Day3Error> {
+    solve(data, 12)
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Battery {
+    joltage: Joltage,
+}
+
+impl From<&char> for Battery {
+    fn from(value: &char) -> Self {
+        match *value >= '1' && *value <= '9' {
+            true => {
+                let joltage = value.to_digit(10).unwrap() as u64;
+
+                Self { joltage }
+            }
+            false => panic!("Invalid digit between 1-9"),
+        }
+    }
+}
+
+pub struct BatteryBank {
+    batteries: Vec<Battery>,
+    // Joltages of the alphabet the bank was parsed with, in ascending order
+    joltages: Vec<Joltage>,
+    // Used to concatenate the joltages of a combination
+    base: Joltage,
+    // Keyed by (start_index, combination_size)
+    combinations_cache: RefCell<HashMap<(usize, usize), Joltage>>,
+}
+
+impl TryFrom<&[u8]> for BatteryBank {
+    type Error = ParseError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        // Validate upfront so the batteries are collected with an exact size
+        if let Some(index) = value.iter().position(|byte| !(b'1'..=b'9').contains(byte)) {
+            return Err(ParseError::InvalidDigit {
+                byte: value[index],
+                index,
+            });
+        }
+
+        let batteries = value
+            .iter()
+            .map(|&byte| Battery {
+                joltage: (byte - b'0') as Joltage,
+            })
+            .collect();
+
+        Ok(Self::decimal(batteries))
+    }
+}
+
+impl fmt::Debug for Battery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.joltage)
+    }
+}
+
+impl fmt::Debug for BatteryBank {
+    /// Only the first batteries are shown, so long banks stay readable
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const MAX_SHOWN_BATTERIES: usize = 20;
+
+        let shown_batteries = self
+            .batteries
+            .iter()
+            .take(MAX_SHOWN_BATTERIES)
+            .map(|battery| format!("{:?}", battery))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let ellipsis = match self.batteries.len() > MAX_SHOWN_BATTERIES {
+            true => ", …",
+            false => "",
+        };
+
+        write!(
+            f,
+            "BatteryBank {{ len: {}, batteries: [{}{}] }}",
+            self.batteries.len(),
+            shown_batteries,
+            ellipsis
+        )
+    }
+}
+
+impl fmt::Display for BatteryBank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for battery in self.batteries.iter() {
+            write!(f, "{}", battery.joltage)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&str> for BatteryBank {
+    fn from(value: &str) -> Self {
+        Self::try_from(value.as_bytes()).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl IntoIterator for BatteryBank {
+    type Item = Battery;
+    type IntoIter = std::vec::IntoIter<Battery>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.batteries.into_iter()
+    }
+}
+
+impl Extend<Battery> for BatteryBank {
+    /// Panics when a battery joltage isn't a digit of the bank alphabet
+    fn extend<T: IntoIterator<Item = Battery>>(&mut self, iter: T) {
+        // Combinations starting anywhere may now pick the new batteries
+        self.combinations_cache.get_mut().clear();
+
+        for battery in iter {
+            assert!(
+                self.joltages.binary_search(&battery.joltage).is_ok(),
+                "Joltage {} isn't a digit of the bank alphabet",
+                battery.joltage
+            );
+
+            self.batteries.push(battery);
+        }
+    }
+}
+
+impl Extend<Joltage> for BatteryBank {
+    fn extend<T: IntoIterator<Item = Joltage>>(&mut self, iter: T) {
+        self.extend(iter.into_iter().map(|joltage| Battery { joltage }));
+    }
+}
+
+impl BatteryBank {
+    #[cfg(debug_assertions)]
+    const CROSS_CHECK_MAX_LEN: usize = 5_000;
+
+    fn new(batteries: Vec<Battery>, base: Joltage, joltages: Vec<Joltage>) -> Self {
+        Self {
+            batteries,
+            joltages,
+            base,
+            combinations_cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Bank of the standard decimal digits, where batteries go from 1 to 9
+    fn decimal(batteries: Vec<Battery>) -> Self {
+        Self::new(batteries, 10, (1..=9).collect())
+    }
+
+    /// Bank sharing the alphabet of this one
+    fn with_batteries(&self, batteries: Vec<Battery>) -> Self {
+        Self::new(batteries, self.base, self.joltages.clone())
+    }
+
+    pub fn parse_with(input: &str, alphabet: &DigitAlphabet) -> Result<Self, ParseError> {
+        let batteries = input
+            .chars()
+            .enumerate()
+            .map(|(index, symbol)| match alphabet.joltage(symbol) {
+                Some(joltage) => Ok(Battery { joltage }),
+                None => Err(ParseError::UnknownSymbol { symbol, index }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self::new(batteries, alphabet.base(), alphabet.joltages()))
+    }
+
+    /// Bank from lines of space-separated single digits, like `"9 8 7\n6 5 4"`
+    pub fn from_lines(s: &str) -> Self {
+        let batteries = s
+            .split_whitespace()
+            .map(|token| {
+                let mut symbols = token.chars();
+
+                match (symbols.next(), symbols.next()) {
+                    (Some(symbol), None) => Battery::from(&symbol),
+                    _ => panic!("Invalid token '{}', expected a single digit", token),
+                }
+            })
+            .collect();
+
+        Self::decimal(batteries)
+    }
+
+    /// Greatest joltage made of `combination_size` batteries kept in order. A single battery
+    /// gives the highest joltage in the bank, and no battery at all gives 0, the empty combination
+    pub fn get_max_joltage_combination(&self, combination_size: usize) -> Joltage {
+        self.try_get_max_joltage_combination(combination_size)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_get_max_joltage_combination(
+        &self,
+        combination_size: usize,
+    ) -> Result<Joltage, SelectionError> {
+        if combination_size > self.batteries.len() {
+            return Err(SelectionError::NotEnoughBatteries {
+                available: self.batteries.len(),
+                requested: combination_size,
+            });
+        }
+
+        self.get_max_joltage_combination_from(0, combination_size)
+    }
+
+    /// Lazily yield the joltages of the max combination, one battery at a time
+    pub fn combination_stream(&self, combination_size: usize) -> impl Iterator<Item = Joltage> {
+        if combination_size > self.batteries.len() {
+            panic!(
+                "{}",
+                SelectionError::NotEnoughBatteries {
+                    available: self.batteries.len(),
+                    requested: combination_size,
+                }
+            );
+        }
+
+        let mut start_index = 0;
+
+        (0..combination_size).map(move |taken_count| {
+            // Ensure enough space to pick the total combination size
+            let final_index = self.batteries.len() - (combination_size - taken_count);
+
+            // Reversed so the first battery wins on ties
+            let (index, battery) = self.batteries[start_index..=final_index]
+                .iter()
+                .enumerate()
+                .rev()
+                .max_by_key(|(_, battery)| battery.joltage)
+                .unwrap();
+
+            start_index += index + 1;
+
+            battery.joltage
+        })
+    }
+
+    /// Max combination within every window of `window_size` consecutive batteries, like `slice::windows`
+    pub fn max_joltage_in_window(
+        &self,
+        window_size: usize,
+        combination_size: usize,
+    ) -> Vec<Joltage> {
+        assert!(window_size > 0, "Window size must be greater than zero");
+
+        if combination_size > window_size {
+            panic!(
+                "{}",
+                SelectionError::NotEnoughBatteries {
+                    available: window_size,
+                    requested: combination_size,
+                }
+            );
+        }
+
+        self.batteries
+            .windows(window_size)
+            .map(|batteries| {
+                Self::compute_max_joltage_combination(batteries, combination_size, self.base)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "{}",
+                            SelectionError::Overflow {
+                                requested: combination_size
+                            }
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    /// Split into consecutive banks of at most `size` batteries, like `slice::chunks`
+    pub fn chunk(&self, size: usize) -> Vec<BatteryBank> {
+        assert!(size > 0, "Chunk size must be greater than zero");
+
+        self.batteries
+            .chunks(size)
+            .map(|batteries| self.with_batteries(batteries.to_vec()))
+            .collect()
+    }
+
+    pub fn reversed(&self) -> BatteryBank {
+        self.with_batteries(self.batteries.iter().rev().cloned().collect())
+    }
+
+    /// Batteries in ascending joltage order
+    pub fn sorted(&self) -> BatteryBank {
+        let mut batteries = self.batteries.clone();
+        batteries.sort_unstable();
+
+        self.with_batteries(batteries)
+    }
+
+    pub fn sorted_descending(&self) -> BatteryBank {
+        let mut batteries = self.batteries.clone();
+        batteries.sort_unstable_by(|a, b| b.cmp(a));
+
+        self.with_batteries(batteries)
+    }
+
+    /// Indices of the batteries with each joltage, in ascending order
+    pub fn group_by_joltage(&self) -> HashMap<Joltage, Vec<usize>> {
+        let mut groups: HashMap<Joltage, Vec<usize>> = HashMap::new();
+
+        for (index, battery) in self.batteries.iter().enumerate() {
+            groups.entry(battery.joltage).or_default().push(index);
+        }
+
+        groups
+    }
+
+    pub fn count_by_joltage(&self) -> HashMap<Joltage, usize> {
+        let mut counts: HashMap<Joltage, usize> = HashMap::new();
+
+        for battery in self.batteries.iter() {
+            *counts.entry(battery.joltage).or_default() += 1;
+        }
+
+        counts
+    }
+
+    /// Distinct joltages of the bank, in ascending order
+    pub fn unique_joltages(&self) -> Vec<Joltage> {
+        let mut joltages: Vec<Joltage> = self
+            .batteries
+            .iter()
+            .map(|battery| battery.joltage)
+            .collect();
+
+        joltages.sort_unstable();
+        joltages.dedup();
+
+        joltages
+    }
+
+    /// Same as `unique_joltages().len()` without sorting. Joltages below 128 fit in a bitset,
+    /// larger alphabets fall back to a set
+    pub fn count_unique_joltages(&self) -> usize {
+        let joltages = self.batteries.iter().map(|battery| battery.joltage);
+
+        match self.base <= u128::BITS as Joltage {
+            true => joltages
+                .fold(0u128, |seen, joltage| seen | 1 << joltage)
+                .count_ones() as usize,
+            false => joltages.collect::<HashSet<Joltage>>().len(),
+        }
+    }
+
+    /// Highest joltage of every window of `window_size` consecutive batteries, from left to
+    /// right. Empty when the window is wider than the bank
+    pub fn window_max_joltages(&self, window_size: usize) -> Vec<Joltage> {
+        assert!(window_size > 0, "Window size must be greater than zero");
+
+        // Indexes of the window batteries that may still become its max, decreasing joltages
+        let mut candidates: VecDeque<usize> = VecDeque::with_capacity(window_size);
+        let mut window_maxes =
+            Vec::with_capacity((self.batteries.len() + 1).saturating_sub(window_size));
+
+        for (index, battery) in self.batteries.iter().enumerate() {
+            // Lower or equal joltages before it can't be the max of any later window
+            while candidates
+                .back()
+                .is_some_and(|&back| self.batteries[back].joltage <= battery.joltage)
+            {
+                candidates.pop_back();
+            }
+
+            candidates.push_back(index);
+
+            if candidates
+                .front()
+                .is_some_and(|&front| front + window_size <= index)
+            {
+                candidates.pop_front();
+            }
+
+            if index + 1 >= window_size {
+                window_maxes.push(self.batteries[candidates[0]].joltage);
+            }
+        }
+
+        window_maxes
+    }
+
+    pub fn average_joltage(&self) -> Option<f64> {
+        if self.batteries.is_empty() {
+            return None;
+        }
+
+        let total: Joltage = self.batteries.iter().map(|battery| battery.joltage).sum();
+
+        Some(total as f64 / self.batteries.len() as f64)
+    }
+
+    /// Population variance of the batteries joltage
+    pub fn variance(&self) -> Option<f64> {
+        let average = self.average_joltage()?;
+
+        let squared_deviations: f64 = self
+            .batteries
+            .iter()
+            .map(|battery| (battery.joltage as f64 - average).powi(2))
+            .sum();
+
+        Some(squared_deviations / self.batteries.len() as f64)
+    }
+
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    fn get_max_joltage_combination_from(
+        &self,
+        start_index: usize,
+        combination_size: usize,
+    ) -> Result<Joltage, SelectionError> {
+        let key = (start_index, combination_size);
+
+        if let Some(&joltage) = self.combinations_cache.borrow().get(&key) {
+            return Ok(joltage);
+        }
+
+        let batteries = &self.batteries[start_index..];
+        let joltage = Self::compute_max_joltage_combination(batteries, combination_size, self.base);
+
+        // Cross-check the greedy selection, keeping debug runs on real inputs tolerable
+        #[cfg(debug_assertions)]
+        if batteries.len() <= Self::CROSS_CHECK_MAX_LEN {
+            debug_assert_eq!(
+                joltage,
+                Self::dp_max_joltage_combination(batteries, combination_size, self.base)
+            );
+        }
+
+        let joltage = joltage.ok_or(SelectionError::Overflow {
+            requested: combination_size,
+        })?;
+
+        self.combinations_cache.borrow_mut().insert(key, joltage);
+
+        Ok(joltage)
+    }
+
+    /// Backward DP where `best[j]` is the greatest combination of `j` batteries from the ones
+    /// visited so far, used as a reference for the greedy selection. `None` when the combination
+    /// doesn't fit in a joltage
+    #[cfg(any(test, debug_assertions))]
+    fn dp_max_joltage_combination(
+        batteries: &[Battery],
+        combination_size: usize,
+        base: Joltage,
+    ) -> Option<Joltage> {
+        // Wider integers so candidates larger than the result don't overflow
+        let base = base as u128;
+        let mut best: Vec<u128> = vec![0; combination_size + 1];
+
+        for (visited, battery) in batteries.iter().rev().enumerate() {
+            let joltage = battery.joltage as u128;
+
+            // Descending so best[j - 1] still excludes the current battery
+            for size in (1..=combination_size.min(visited + 1)).rev() {
+                let with_battery = u32::try_from(size - 1)
+                    .ok()
+                    .and_then(|exponent| base.checked_pow(exponent))
+                    .and_then(|weight| joltage.checked_mul(weight))
+                    .and_then(|value| value.checked_add(best[size - 1]))?;
+
+                best[size] = match size <= visited {
+                    true => best[size].max(with_battery),
+                    false => with_battery,
+                };
+            }
+        }
+
+        Joltage::try_from(best[combination_size]).ok()
+    }
+
+    /// `None` when the combination doesn't fit in a joltage
+    fn compute_max_joltage_combination(
+        batteries: &[Battery],
+        combination_size: usize,
+        base: Joltage,
+    ) -> Option<Joltage> {
+        let mut batteries_joltage_taken: Vec<Joltage> = Vec::with_capacity(combination_size);
+
+        for (index, battery) in batteries.iter().enumerate() {
+            let remaining_batteries = batteries.len() - index;
+
+            // Replace smaller picks as long as there are enough batteries left to fill the combination
+            while let Some(&last_joltage) = batteries_joltage_taken.last()
+                && last_joltage < battery.joltage
+                && batteries_joltage_taken.len() - 1 + remaining_batteries >= combination_size
+            {
+                batteries_joltage_taken.pop();
+            }
+
+            if batteries_joltage_taken.len() < combination_size {
+                batteries_joltage_taken.push(battery.joltage);
+            }
+        }
+
+        // Create the number from digits concatenation
+        batteries_joltage_taken
+            .iter()
+            .try_fold(0 as Joltage, |acc, joltage| {
+                acc.checked_mul(base)?.checked_add(*joltage)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    use crate::generator::{Distribution, random_bank_with};
+
+    // Previous implementation, rescanning the remaining window on every pick
+    fn rescan_max_joltage_combination(
+        battery_bank: &BatteryBank,
+        combination_size: usize,
+    ) -> Joltage {
+        let batteries = &battery_bank.batteries;
+        let mut batteries_joltage_taken = Vec::with_capacity(combination_size);
+        let mut initial_index = 0;
+
+        while batteries_joltage_taken.len() < combination_size {
+            let final_index = batteries.len() - (combination_size - batteries_joltage_taken.len());
+
+            let max_joltage = batteries[initial_index..=final_index]
+                .iter()
+                .max()
+                .unwrap()
+                .joltage;
+
+            batteries_joltage_taken.push(max_joltage);
+
+            initial_index += batteries[initial_index..]
+                .iter()
+                .position(|battery| battery.joltage == max_joltage)
+                .unwrap()
+                + 1;
+        }
+
+        batteries_joltage_taken
+            .iter()
+            .fold(0, |acc, joltage| acc * 10 + joltage)
+    }
+
+    #[test]
+    fn parse_battery_bank() {
+        let data = [9, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1, 1, 1, 1];
+
+        let batteries: Vec<Battery> = data.iter().map(|item| Battery { joltage: *item }).collect();
+        let raw_data: String = data.iter().map(|item| item.to_string()).collect();
+
+        assert_eq!(BatteryBank::from(raw_data.as_str()).batteries, batteries);
+    }
+
+    #[test]
+    fn debug_battery_bank() {
+        let battery_bank = BatteryBank::from("98765");
+
+        assert_eq!(
+            format!("{:?}", battery_bank),
+            "BatteryBank { len: 5, batteries: [9, 8, 7, 6, 5] }"
+        );
+        assert_eq!(format!("{:?}", battery_bank.batteries[0]), "9");
+
+        let battery_bank = BatteryBank::from("1234567891234567891234567");
+        let debug_output = format!("{:?}", battery_bank);
+
+        assert!(debug_output.starts_with("BatteryBank { len: 25, batteries: [1, 2, 3"));
+        assert!(debug_output.ends_with("1, 2, …] }"));
+        assert_eq!(debug_output.matches(", ").count(), 21);
+    }
+
+    #[test]
+    fn parse_battery_bank_bytes() {
+        let battery_bank = BatteryBank::try_from(b"9871".as_slice()).unwrap();
+
+        assert_eq!(
+            battery_bank.batteries,
+            [9, 8, 7, 1].map(|joltage| Battery { joltage })
+        );
+        assert_eq!(battery_bank.batteries, BatteryBank::from("9871").batteries);
+    }
+
+    #[test]
+    fn parse_battery_bank_with_alphabet() {
+        let alphabet = DigitAlphabet::new(('a'..='i').zip(1..)).unwrap();
+
+        let battery_bank = BatteryBank::parse_with("ihgfedcba", &alphabet).unwrap();
+        let digits_battery_bank = BatteryBank::from("987654321");
+
+        assert_eq!(battery_bank.batteries, digits_battery_bank.batteries);
+
+        for combination_size in [1, 2, 5, 9] {
+            assert_eq!(
+                battery_bank.get_max_joltage_combination(combination_size),
+                digits_battery_bank.get_max_joltage_combination(combination_size)
+            );
+        }
+
+        assert_eq!(
+            BatteryBank::parse_with("ihz", &alphabet).err(),
+            Some(ParseError::UnknownSymbol {
+                symbol: 'z',
+                index: 2
+            })
+        );
+
+        assert!(BatteryBank::parse_with("987", &DigitAlphabet::default()).is_ok());
+
+        // The default alphabet rejects '0' like the byte parser does
+        assert_eq!(
+            BatteryBank::parse_with("907", &DigitAlphabet::default()).err(),
+            Some(ParseError::UnknownSymbol {
+                symbol: '0',
+                index: 1
+            })
+        );
+        assert!(BatteryBank::try_from("907".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn parse_battery_bank_invalid_bytes() {
+        assert_eq!(
+            BatteryBank::try_from(b"98\xFF1".as_slice()).err(),
+            Some(ParseError::InvalidDigit {
+                byte: 0xFF,
+                index: 2
+            })
+        );
+
+        assert_eq!(
+            BatteryBank::try_from(b"0".as_slice()).err(),
+            Some(ParseError::InvalidDigit {
+                byte: b'0',
+                index: 0
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid digit between 1-9")]
+    fn parse_battery_bank_invalid_str() {
+        let _ = BatteryBank::from("98a1");
+    }
+
+    #[test]
+    fn parse_banks_strict() {
+        let data = b"987654321111111\n811111111111119\n";
+
+        assert_eq!(parse_banks(data).unwrap().len(), 2);
+
+        let data = b"987654321111111\n81111x111111119\n2342342342342a8";
+
+        assert_eq!(
+            parse_banks(data).err(),
+            Some(LineError {
+                line_number: 2,
+                raw: String::from("81111x111111119"),
+                error: ParseError::InvalidDigit {
+                    byte: b'x',
+                    index: 5
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn parse_banks_collecting_errors() {
+        let data =
+            b"987654321111111\n81111x111111119\n234234234234278\n\xFF\n9876\n818181911112111\n";
+
+        let (batteries_banks, errors) = parse_banks_lenient(data, 12);
+
+        assert_eq!(batteries_banks.len(), 3);
+
+        let error_lines: Vec<usize> = errors.iter().map(|error| error.line_number).collect();
+        assert_eq!(error_lines, Vec::from([2, 4, 5]));
+        assert_eq!(
+            errors[2],
+            LineError {
+                line_number: 5,
+                raw: String::from("9876"),
+                error: ParseError::TooShort {
+                    len: 4,
+                    min_len: 12
+                },
+            }
+        );
+        assert_eq!(
+            errors[2].to_string(),
+            "Line 5 (\"9876\"): Bank of 4 batteries, at least 12 are needed"
+        );
+
+        let part_1: Joltage = batteries_banks
+            .iter()
+            .map(|(_, battery_bank)| battery_bank.get_max_joltage_combination(2))
+            .sum();
+        assert_eq!(part_1, 98 + 78 + 92);
+
+        let part_2: Joltage = batteries_banks
+            .iter()
+            .map(|(_, battery_bank)| battery_bank.get_max_joltage_combination(12))
+            .sum();
+        assert_eq!(part_2, 987654321111 + 434234234278 + 888911112111);
+
+        // Short banks are fine when only smaller combinations are picked
+        let (batteries_banks, errors) = parse_banks_lenient(data, 2);
+
+        assert_eq!(batteries_banks.len(), 4);
+        assert_eq!(errors.len(), 2);
+
+        // Banks keep the line they come from, past the skipped ones
+        let line_numbers: Vec<usize> = batteries_banks
+            .iter()
+            .map(|(line_number, _)| *line_number)
+            .collect();
+        assert_eq!(line_numbers, Vec::from([1, 3, 5, 6]));
+    }
+
+    #[test]
+    fn get_max_joltage_combination() {
+        let battery_bank_raw = "987654321111111";
+        assert_eq!(
+            BatteryBank::from(battery_bank_raw).get_max_joltage_combination(2),
+            98
+        );
+        assert_eq!(
+            BatteryBank::from(battery_bank_raw).get_max_joltage_combination(12),
+            987654321111
+        );
+
+        let battery_bank_raw = "811111111111119";
+        assert_eq!(
+            BatteryBank::from(battery_bank_raw).get_max_joltage_combination(2),
+            89
+        );
+        assert_eq!(
+            BatteryBank::from(battery_bank_raw).get_max_joltage_combination(12),
+            811111111119
+        );
+
+        let battery_bank_raw = "234234234234278";
+        assert_eq!(
+            BatteryBank::from(battery_bank_raw).get_max_joltage_combination(2),
+            78
+        );
+        assert_eq!(
+            BatteryBank::from(battery_bank_raw).get_max_joltage_combination(12),
+            434234234278
+        );
+
+        let battery_bank_raw = "818181911112111";
+        assert_eq!(
+            BatteryBank::from(battery_bank_raw).get_max_joltage_combination(2),
+            92
+        );
+        assert_eq!(
+            BatteryBank::from(battery_bank_raw).get_max_joltage_combination(12),
+            888911112111
+        );
+    }
+
+    #[test]
+    fn get_max_joltage_combination_edge_sizes() {
+        let battery_bank = BatteryBank::from("818181911112111");
+
+        assert_eq!(battery_bank.get_max_joltage_combination(0), 0);
+        assert_eq!(battery_bank.get_max_joltage_combination(1), 9);
+        assert_eq!(BatteryBank::from("1234").get_max_joltage_combination(1), 4);
+        assert_eq!(BatteryBank::from("7").get_max_joltage_combination(1), 7);
+
+        // Nothing to pick from is only a problem when something is requested
+        let empty_bank = BatteryBank::from("");
+
+        assert_eq!(empty_bank.try_get_max_joltage_combination(0), Ok(0));
+        assert_eq!(
+            empty_bank.try_get_max_joltage_combination(1),
+            Err(SelectionError::NotEnoughBatteries {
+                available: 0,
+                requested: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn battery_bank_from_lines() {
+        let examples = [
+            ("9 8 7\n6 5 4", "987654"),
+            ("8 1 1 1 1 1 1 1 1 1 1 1 1 1 9", "811111111111119"),
+            ("2 3 4\n2 3 4\n\n2 3 4 2 3 4 2 7 8\n", "234234234234278"),
+            ("  5 ", "5"),
+        ];
+
+        for (battery_bank_lines, battery_bank_raw) in examples {
+            let from_lines = BatteryBank::from_lines(battery_bank_lines);
+            let from_raw = BatteryBank::from(battery_bank_raw);
+
+            assert_eq!(from_lines.batteries, from_raw.batteries);
+            assert_eq!(from_lines.base, from_raw.base);
+            assert_eq!(
+                from_lines.get_max_joltage_combination(1),
+                from_raw.get_max_joltage_combination(1)
+            );
+        }
+
+        assert!(BatteryBank::from_lines("").batteries.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid digit between 1-9")]
+    fn battery_bank_from_lines_invalid_digit() {
+        BatteryBank::from_lines("9 0 7");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid token '98', expected a single digit")]
+    fn battery_bank_from_lines_multi_digit_token() {
+        BatteryBank::from_lines("98 7");
+    }
+
+    #[test]
+    fn reversed_and_sorted_banks() {
+        let examples = [
+            "987654321111111",
+            "811111111111119",
+            "234234234234278",
+            "818181911112111",
+        ];
+
+        for battery_bank_raw in examples {
+            let battery_bank = BatteryBank::from(battery_bank_raw);
+
+            assert_eq!(
+                battery_bank.sorted().sorted().batteries,
+                battery_bank.sorted().batteries
+            );
+            assert_eq!(
+                battery_bank.reversed().reversed().batteries,
+                battery_bank.batteries
+            );
+            assert!(battery_bank.sorted().batteries.is_sorted());
+            assert_eq!(
+                battery_bank.sorted_descending().batteries,
+                battery_bank.sorted().reversed().batteries
+            );
+
+            // The original bank is left untouched
+            assert_eq!(battery_bank.to_string(), battery_bank_raw);
+
+            // Sorting lets the combination pick the highest batteries regardless of their
+            // position, so it can only do better than the original order
+            let sorted_descending = battery_bank.sorted_descending();
+
+            for combination_size in [2, 12] {
+                let best_batteries: String = sorted_descending
+                    .to_string()
+                    .chars()
+                    .take(combination_size)
+                    .collect();
+
+                assert_eq!(
+                    sorted_descending.get_max_joltage_combination(combination_size),
+                    best_batteries.parse::<Joltage>().unwrap()
+                );
+                assert!(
+                    sorted_descending.get_max_joltage_combination(combination_size)
+                        >= battery_bank.get_max_joltage_combination(combination_size)
+                );
+            }
+        }
+
+        // Already in descending order, so both give the same result
+        let battery_bank = BatteryBank::from("987654321111111");
+
+        assert_eq!(
+            battery_bank
+                .sorted_descending()
+                .get_max_joltage_combination(12),
+            battery_bank.get_max_joltage_combination(12)
+        );
+        assert_eq!(
+            BatteryBank::from("811111111111119")
+                .sorted_descending()
+                .get_max_joltage_combination(2),
+            98
+        );
+    }
+
+    #[test]
+    fn single_pass_matches_rescan() {
+        let examples = [
+            "987654321111111",
+            "811111111111119",
+            "234234234234278",
+            "818181911112111",
+        ];
+
+        for battery_bank_raw in examples {
+            let battery_bank = BatteryBank::from(battery_bank_raw);
+
+            for combination_size in [1, 2, 12, 15] {
+                assert_eq!(
+                    battery_bank.get_max_joltage_combination(combination_size),
+                    rescan_max_joltage_combination(&battery_bank, combination_size)
+                );
+            }
+        }
+
+        let distributions = [
+            Distribution::Uniform,
+            Distribution::Heavy(9),
+            Distribution::Decreasing,
+        ];
+
+        for (seed, distribution) in distributions.into_iter().cycle().take(30).enumerate() {
+            let battery_bank =
+                BatteryBank::from(random_bank_with(40, seed as u64, distribution).as_str());
+
+            for combination_size in [2, 12] {
+                assert_eq!(
+                    battery_bank.get_max_joltage_combination(combination_size),
+                    rescan_max_joltage_combination(&battery_bank, combination_size)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn dp_max_joltage_combination() {
+        let examples = [
+            ("987654321111111", 98, 987654321111),
+            ("811111111111119", 89, 811111111119),
+            ("234234234234278", 78, 434234234278),
+            ("818181911112111", 92, 888911112111),
+            ("191919191919191", 99, 999191919191),
+        ];
+
+        for (battery_bank_raw, part_1, part_2) in examples {
+            let battery_bank = BatteryBank::from(battery_bank_raw);
+            let batteries = &battery_bank.batteries;
+
+            assert_eq!(
+                BatteryBank::dp_max_joltage_combination(batteries, 2, 10),
+                Some(part_1)
+            );
+            assert_eq!(
+                BatteryBank::dp_max_joltage_combination(batteries, 12, 10),
+                Some(part_2)
+            );
+        }
+
+        // Repeated maxima, in a bank too short for the second part
+        let battery_bank = BatteryBank::from("999919999");
+        assert_eq!(
+            BatteryBank::dp_max_joltage_combination(&battery_bank.batteries, 2, 10),
+            Some(99)
+        );
+        assert_eq!(
+            BatteryBank::dp_max_joltage_combination(&battery_bank.batteries, 8, 10),
+            Some(99999999)
+        );
+        assert_eq!(
+            BatteryBank::dp_max_joltage_combination(&battery_bank.batteries, 0, 10),
+            Some(0)
+        );
+
+        // Sizes past the joltage digits, even past the wider integers, don't fit
+        let battery_bank = BatteryBank::from("9".repeat(50).as_str());
+        assert_eq!(
+            BatteryBank::dp_max_joltage_combination(&battery_bank.batteries, 19, 10),
+            Some(9_999_999_999_999_999_999)
+        );
+        assert_eq!(
+            BatteryBank::dp_max_joltage_combination(&battery_bank.batteries, 20, 10),
+            None
+        );
+        assert_eq!(
+            BatteryBank::dp_max_joltage_combination(&battery_bank.batteries, 45, 10),
+            None
+        );
+    }
+
+    #[test]
+    fn get_max_joltage_combination_overflow() {
+        let battery_bank = BatteryBank::from("9".repeat(50).as_str());
+
+        assert_eq!(
+            battery_bank.try_get_max_joltage_combination(19),
+            Ok(9_999_999_999_999_999_999)
+        );
+        assert_eq!(
+            battery_bank.try_get_max_joltage_combination(20),
+            Err(SelectionError::Overflow { requested: 20 })
+        );
+        assert_eq!(
+            battery_bank.try_get_max_joltage_combination(45),
+            Err(SelectionError::Overflow { requested: 45 })
+        );
+    }
+
+    #[test]
+    fn combination_cache() {
+        let battery_bank = BatteryBank::from("818181911112111");
+
+        assert_eq!(battery_bank.get_max_joltage_combination(2), 92);
+        assert_eq!(battery_bank.get_max_joltage_combination(12), 888911112111);
+
+        let cache = battery_bank.combinations_cache.borrow();
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&(0, 2)), Some(&92));
+        assert_eq!(cache.get(&(0, 12)), Some(&888911112111));
+    }
+
+    #[test]
+    fn stream_combination() {
+        let examples = [
+            "987654321111111",
+            "811111111111119",
+            "234234234234278",
+            "818181911112111",
+        ];
+
+        for battery_bank_raw in examples {
+            let battery_bank = BatteryBank::from(battery_bank_raw);
+
+            let combination = battery_bank.get_max_joltage_combination(12);
+            let leading_digits: Vec<Joltage> =
+                battery_bank.combination_stream(12).take(2).collect();
+
+            assert_eq!(
+                leading_digits,
+                Vec::from([
+                    combination / 10_u64.pow(11),
+                    combination / 10_u64.pow(10) % 10
+                ])
+            );
+
+            let streamed_combination = battery_bank
+                .combination_stream(12)
+                .fold(0, |acc, joltage| acc * 10 + joltage);
+
+            assert_eq!(streamed_combination, combination);
+        }
+    }
+
+    #[test]
+    fn max_joltage_in_sliding_window() {
+        let battery_bank = BatteryBank::from("818181911112111");
+
+        let window_joltages = battery_bank.max_joltage_in_window(5, 2);
+
+        assert_eq!(window_joltages.len(), 11);
+        // "81818" and "12111"
+        assert_eq!(window_joltages.first(), Some(&88));
+        assert_eq!(window_joltages.last(), Some(&21));
+        assert_eq!(window_joltages.iter().max(), Some(&91));
+
+        assert_eq!(
+            battery_bank.max_joltage_in_window(15, 12),
+            Vec::from([battery_bank.get_max_joltage_combination(12)])
+        );
+        assert!(battery_bank.max_joltage_in_window(16, 2).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough batteries")]
+    fn max_joltage_in_window_too_small() {
+        BatteryBank::from("987654321").max_joltage_in_window(2, 3);
+    }
+
+    #[test]
+    fn chunk_battery_bank() {
+        let battery_bank = BatteryBank::from("987654321111111");
+
+        let chunks = battery_bank.chunk(5);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|chunk| chunk.batteries.len() == 5));
+
+        let chunks = battery_bank.chunk(4);
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(chunks.last().unwrap().batteries.len(), 3);
+
+        let flattened_batteries: Vec<Battery> = chunks
+            .into_iter()
+            .flat_map(|chunk| chunk.batteries)
+            .collect();
+        assert_eq!(flattened_batteries, battery_bank.batteries);
+
+        assert_eq!(battery_bank.chunk(20).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Chunk size must be greater than zero")]
+    fn chunk_battery_bank_zero_size() {
+        BatteryBank::from("987").chunk(0);
+    }
+
+    #[test]
+    fn extend_battery_bank() {
+        let mut battery_bank = BatteryBank::from("818181911112111");
+
+        // Cache the combination before extending, so a stale value would show up
+        assert_eq!(battery_bank.get_max_joltage_combination(2), 92);
+
+        let other_bank = BatteryBank::from("99");
+        let (len, other_len) = (battery_bank.batteries.len(), other_bank.batteries.len());
+
+        battery_bank.extend(other_bank);
+
+        assert_eq!(battery_bank.batteries.len(), len + other_len);
+        assert_eq!(battery_bank.to_string(), "81818191111211199");
+        assert_eq!(battery_bank.get_max_joltage_combination(2), 99);
+
+        let batteries = Vec::from([Battery { joltage: 1 }, Battery { joltage: 7 }]);
+        let len = battery_bank.batteries.len();
+
+        battery_bank.extend(batteries);
+
+        assert_eq!(battery_bank.batteries.len(), len + 2);
+        assert_eq!(battery_bank.to_string(), "8181819111121119917");
+
+        let mut battery_bank = BatteryBank::from("123");
+        battery_bank.extend([4 as Joltage, 5, 6]);
+
+        assert_eq!(battery_bank.batteries.len(), 6);
+        assert_eq!(battery_bank.to_string(), "123456");
+
+        let alphabet = DigitAlphabet::new([('a', 10), ('b', 20)]).unwrap();
+        let mut battery_bank = BatteryBank::parse_with("ab", &alphabet).unwrap();
+        battery_bank.extend([20 as Joltage, 10]);
+
+        assert_eq!(battery_bank.unique_joltages(), Vec::from([10, 20]));
+        assert_eq!(battery_bank.batteries.len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Joltage 0 isn't a digit of the bank alphabet")]
+    fn extend_battery_bank_zero_joltage() {
+        BatteryBank::from("123").extend([0 as Joltage]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Joltage 10 isn't a digit of the bank alphabet")]
+    fn extend_battery_bank_out_of_range_joltage() {
+        BatteryBank::from("123").extend([Battery { joltage: 10 }]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Joltage 15 isn't a digit of the bank alphabet")]
+    fn extend_battery_bank_outside_alphabet() {
+        let alphabet = DigitAlphabet::new([('a', 10), ('b', 20)]).unwrap();
+
+        BatteryBank::parse_with("ab", &alphabet)
+            .unwrap()
+            .extend([15 as Joltage]);
+    }
+
+    #[test]
+    fn group_batteries_by_joltage() {
+        let battery_bank = BatteryBank::from("818181911112111");
+
+        let groups = battery_bank.group_by_joltage();
+
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups[&8], Vec::from([0, 2, 4]));
+        assert_eq!(groups[&9], Vec::from([6]));
+        assert_eq!(groups[&2], Vec::from([11]));
+        assert_eq!(groups[&1].len(), 10);
+        assert!(groups[&1].is_sorted());
+        assert!(!groups.contains_key(&7));
+
+        let counts = battery_bank.count_by_joltage();
+
+        assert_eq!(counts, HashMap::from([(1, 10), (2, 1), (8, 3), (9, 1)]));
+        assert!(BatteryBank::from("").count_by_joltage().is_empty());
+    }
+
+    #[test]
+    fn unique_joltages() {
+        let examples = [
+            ("7777", Vec::from([7])),
+            ("975312", Vec::from([1, 2, 3, 5, 7, 9])),
+            ("818181911112111", Vec::from([1, 2, 8, 9])),
+            ("", Vec::new()),
+        ];
+
+        for (battery_bank_raw, expected_joltages) in examples {
+            let battery_bank = BatteryBank::from(battery_bank_raw);
+
+            assert_eq!(battery_bank.unique_joltages(), expected_joltages);
+            assert_eq!(
+                battery_bank.count_unique_joltages(),
+                expected_joltages.len()
+            );
+            assert_eq!(
+                battery_bank.count_unique_joltages(),
+                battery_bank.count_by_joltage().len()
+            );
+        }
+
+        let alphabet = DigitAlphabet::new([('a', 10), ('b', 20)]).unwrap();
+        let battery_bank = BatteryBank::parse_with("babba", &alphabet).unwrap();
+
+        assert_eq!(battery_bank.unique_joltages(), Vec::from([10, 20]));
+        assert_eq!(battery_bank.count_unique_joltages(), 2);
+
+        // Too large for the bitset
+        let alphabet = DigitAlphabet::new([('a', 1000), ('b', 200), ('c', 7)]).unwrap();
+        let battery_bank = BatteryBank::parse_with("abacab", &alphabet).unwrap();
+
+        assert_eq!(battery_bank.count_unique_joltages(), 3);
+        assert_eq!(
+            battery_bank.count_unique_joltages(),
+            battery_bank.unique_joltages().len()
+        );
+    }
+
+    #[test]
+    fn window_max_joltages() {
+        let battery_bank = BatteryBank::from("818181911112111");
+
+        // Single battery windows are the joltages themselves
+        let joltages = battery_bank.window_max_joltages(1);
+
+        assert_eq!(
+            joltages,
+            Vec::from([8, 1, 8, 1, 8, 1, 9, 1, 1, 1, 1, 2, 1, 1, 1])
+        );
+
+        let mut unique_joltages = joltages.clone();
+        unique_joltages.sort_unstable();
+        unique_joltages.dedup();
+
+        assert_eq!(unique_joltages, battery_bank.unique_joltages());
+
+        // A window as wide as the bank only has the bank max
+        assert_eq!(battery_bank.window_max_joltages(15), Vec::from([9]));
+        assert!(battery_bank.window_max_joltages(16).is_empty());
+
+        // Maxes leaving the window and repeated joltages
+        assert_eq!(
+            BatteryBank::from("9123456781").window_max_joltages(3),
+            Vec::from([9, 3, 4, 5, 6, 7, 8, 8])
+        );
+        assert_eq!(
+            BatteryBank::from("5553555").window_max_joltages(2),
+            Vec::from([5, 5, 5, 5, 5, 5])
+        );
+        assert_eq!(
+            BatteryBank::from("987654321").window_max_joltages(4),
+            Vec::from([9, 8, 7, 6, 5, 4])
+        );
+        assert!(BatteryBank::from("").window_max_joltages(1).is_empty());
+
+        // Same as taking the max of every window directly
+        let battery_bank = BatteryBank::from("234234234234278");
+
+        for window_size in 1..=15 {
+            assert_eq!(
+                battery_bank.window_max_joltages(window_size),
+                battery_bank
+                    .batteries
+                    .windows(window_size)
+                    .map(|window| window.iter().map(|battery| battery.joltage).max().unwrap())
+                    .collect::<Vec<Joltage>>()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Window size must be greater than zero")]
+    fn window_max_joltages_zero_size() {
+        let _ = BatteryBank::from("123").window_max_joltages(0);
+    }
+
+    #[test]
+    fn joltage_statistics() {
+        const EPSILON: f64 = 1e-9;
+
+        let battery_bank = BatteryBank::from("12345");
+
+        assert!((battery_bank.average_joltage().unwrap() - 3.0).abs() < EPSILON);
+        assert!((battery_bank.variance().unwrap() - 2.0).abs() < EPSILON);
+        assert!((battery_bank.std_dev().unwrap() - 2f64.sqrt()).abs() < EPSILON);
+
+        let battery_bank = BatteryBank::from("");
+
+        assert_eq!(battery_bank.average_joltage(), None);
+        assert_eq!(battery_bank.variance(), None);
+        assert_eq!(battery_bank.std_dev(), None);
+    }
+
+    #[test]
+    fn find_best_and_worst_banks() {
+        let data = b"234234234234278\n987654321111111\n111111111111111\n987654321111111\n111111111111111\n";
+        let batteries_banks = parse_numbered_banks(data).unwrap();
+
+        let (best_bank, worst_bank) = best_and_worst_banks(&batteries_banks, 12).unwrap();
+
+        assert_eq!(best_bank.line_number, 2);
+        assert_eq!(best_bank.bank.to_string(), "987654321111111");
+        assert_eq!(best_bank.combination, 987654321111);
+
+        assert_eq!(worst_bank.line_number, 3);
+        assert_eq!(worst_bank.bank.to_string(), "111111111111111");
+        assert_eq!(worst_bank.combination, 111111111111);
+
+        assert!(best_and_worst_banks(&[], 12).is_none());
+
+        // Skipped lines before the best bank don't shift its line
+        let data = b"1111x1111111111\n234234234234278\n\n9876\n987654321111111\n";
+        let (batteries_banks, errors) = parse_banks_lenient(data, 12);
+
+        assert_eq!(errors.len(), 2);
+
+        let (best_bank, worst_bank) = best_and_worst_banks(&batteries_banks, 12).unwrap();
+
+        assert_eq!(best_bank.line_number, 5);
+        assert_eq!(best_bank.combination, 987654321111);
+        assert_eq!(worst_bank.line_number, 2);
+    }
+
+    #[test]
+    fn solve_example_banks() {
+        let data = "987654321111111\n811111111111119\n234234234234278\n818181911112111\n";
+
+        assert_eq!(solve_part1(data), Ok(357));
+        assert_eq!(solve_part2(data), Ok(3121910778619));
+    }
+
+    #[test]
+    fn stream_bank_results() {
+        let data = "987654321111111\n811111111111119\n\n234234234234278\n";
+        let mut reader = Cursor::new(data);
+
+        let bank_results = results_iter(&mut reader, &[2, 12]);
+        drop(bank_results);
+
+        // Nothing is read until the first item is requested
+        assert_eq!(reader.position(), 0);
+
+        let mut bank_results = results_iter(&mut reader, &[2, 12]);
+
+        assert_eq!(
+            bank_results.next(),
+            Some(Ok(BankResult {
+                line_number: 1,
+                values: Vec::from([98, 987654321111])
+            }))
+        );
+
+        drop(bank_results);
+        assert_eq!(reader.position(), 16);
+
+        let mut bank_results = results_iter(&mut reader, &[2, 12]);
+
+        assert_eq!(
+            bank_results.next(),
+            Some(Ok(BankResult {
+                line_number: 1,
+                values: Vec::from([89, 811111111119])
+            }))
+        );
+        assert_eq!(
+            bank_results.next(),
+            Some(Ok(BankResult {
+                line_number: 3,
+                values: Vec::from([78, 434234234278])
+            }))
+        );
+        assert_eq!(bank_results.next(), None);
+        assert_eq!(bank_results.next(), None);
+    }
+
+    #[test]
+    fn stream_bank_results_errors() {
+        let data = "987654321111111\n8111111111111x9\n234234234234278\n98765\n";
+
+        let results: Vec<Result<BankResult, Day3Error>> =
+            results_iter(Cursor::new(data), &[2, 12]).collect();
+
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(Day3Error::Parse(LineError { line_number: 2, .. }))
+        ));
+        assert!(results[2].is_ok());
+        assert!(matches!(
+            results[3],
+            Err(Day3Error::Selection { line_number: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn comma_separated_banks() {
+        let newline_data = "987654321111111\n811111111111119\n";
+        let comma_data = "987654321111111, 811111111111119,";
+        let mixed_data = "987654321111111,,\n,811111111111119\n";
+
+        for data in [comma_data, mixed_data] {
+            assert_eq!(solve_part1(data), solve_part1(newline_data));
+            assert_eq!(solve_part2(data), solve_part2(newline_data));
+            assert_eq!(parse_banks(data.as_bytes()).unwrap().len(), 2);
+        }
+
+        let results: Vec<Result<BankResult, Day3Error>> =
+            results_iter(Cursor::new(mixed_data), &[2]).collect();
+
+        assert_eq!(
+            results,
+            Vec::from([
+                Ok(BankResult {
+                    line_number: 1,
+                    values: Vec::from([98])
+                }),
+                Ok(BankResult {
+                    line_number: 2,
+                    values: Vec::from([89])
+                })
+            ])
+        );
+    }
+
+    #[test]
+    fn solve_errors() {
+        let data = "987654321111111\n8111111111111x9\n";
+
+        assert_eq!(
+            solve_part1(data),
+            Err(Day3Error::Parse(LineError {
+                line_number: 2,
+                raw: String::from("8111111111111x9"),
+                error: ParseError::InvalidDigit {
+                    byte: b'x',
+                    index: 13
+                },
+            }))
+        );
+
+        let data = "987654321111111\n\n98765\n";
+
+        assert_eq!(solve_part1(data), Ok(98 + 98));
+        assert_eq!(
+            solve_part2(data),
+            Err(Day3Error::Selection {
+                line_number: 3,
+                error: SelectionError::NotEnoughBatteries {
+                    available: 5,
+                    requested: 12
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! Regression answers for a medium fixture of 500 banks with 40 batteries each,
+//! produced once with `generator::random_banks(500, 40, 2025)` and committed.
+
+use day_3::{solve_part1, solve_part2};
+
+const FIXTURE: &str = include_str!("data/banks_500x40.txt");
+
+#[test]
+fn medium_fixture_part1() {
+    assert_eq!(solve_part1(FIXTURE), Ok(49436));
+}
+
+#[test]
+fn medium_fixture_part2() {
+    assert_eq!(solve_part2(FIXTURE), Ok(497037139186181));
+}
@@ -0,0 +1,175 @@
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use day_4::{
+    AccessibilityScan, BitGrid, ForkLiftsHelper, Grid, GridStore, Pos,
+    generator::{random_grid, random_warehouse},
+};
+
+fn peel(c: &mut Criterion) {
+    let data = random_grid(2_000, 2_000, 65, 42);
+
+    let mut group = c.benchmark_group("peel_2000x2000");
+    group.sample_size(10);
+
+    group.bench_function("incremental", |b| {
+        b.iter_batched(
+            || Grid::from(data.as_str()),
+            |mut grid| ForkLiftsHelper::new(&mut grid).run_with_history(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("queue_based", |b| {
+        b.iter_batched(
+            || Grid::from(data.as_str()),
+            |mut grid| ForkLiftsHelper::new(&mut grid).run_queue_based(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    // Full rescan of the grid on every round
+    group.bench_function("rescan", |b| {
+        b.iter_batched(
+            || Grid::from(data.as_str()),
+            |mut grid| {
+                let mut history = Vec::new();
+
+                loop {
+                    let valid_paper_rolls: Vec<Pos> = AccessibilityScan::new(&grid)
+                        .find_accessible_paper_rolls()
+                        .collect();
+
+                    if valid_paper_rolls.is_empty() {
+                        return history;
+                    }
+
+                    for pos in valid_paper_rolls.iter() {
+                        grid.remove_item(pos.row, pos.col);
+                    }
+
+                    history.push(valid_paper_rolls);
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn peel_dense(c: &mut Criterion) {
+    let grid = random_warehouse(3_000, 3_000, 0.9, 42);
+
+    let mut group = c.benchmark_group("peel_3000x3000_dense");
+    group.sample_size(10);
+
+    group.bench_function("sequential", |b| {
+        b.iter_batched(
+            || grid.clone(),
+            |mut grid| ForkLiftsHelper::new(&mut grid).run_with_history(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("queue_based", |b| {
+        b.iter_batched(
+            || grid.clone(),
+            |mut grid| ForkLiftsHelper::new(&mut grid).run_queue_based(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("double_buffered", |b| {
+        b.iter_batched(
+            || grid.clone(),
+            |mut grid| ForkLiftsHelper::new(&mut grid).run_double_buffered(),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn peel_by_density(c: &mut Criterion) {
+    let mut group = c.benchmark_group("peel_500x500");
+
+    for density in [0.3, 0.6, 0.9] {
+        group.bench_with_input(
+            BenchmarkId::new("grid", density),
+            &density,
+            |b, &density| {
+                b.iter_batched(
+                    || random_warehouse(500, 500, density, 42),
+                    |mut grid| ForkLiftsHelper::new(&mut grid).run_with_history(),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("bitset", density),
+            &density,
+            |b, &density| {
+                b.iter_batched(
+                    || BitGrid::from(&random_warehouse(500, 500, density, 42)),
+                    |mut bit_grid| ForkLiftsHelper::new(&mut bit_grid).run_with_history(),
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn accessibility_scan(c: &mut Criterion) {
+    let grid = random_warehouse(2_000, 2_000, 0.65, 42);
+
+    let mut group = c.benchmark_group("scan_2000x2000");
+    group.sample_size(10);
+
+    group.bench_function("find_accessible_paper_rolls", |b| {
+        b.iter(|| {
+            AccessibilityScan::new(&grid)
+                .find_accessible_paper_rolls()
+                .len()
+        })
+    });
+
+    let bit_grid = BitGrid::from(&grid);
+
+    group.bench_function("find_accessible_paper_rolls_bitset", |b| {
+        b.iter(|| {
+            AccessibilityScan::new(&bit_grid)
+                .find_accessible_paper_rolls()
+                .len()
+        })
+    });
+
+    group.finish();
+}
+
+fn dense_accessibility_check(c: &mut Criterion) {
+    let grid = random_warehouse(1_000, 1_000, 0.9, 42);
+
+    let mut group = c.benchmark_group("check_1000x1000_dense");
+
+    group.bench_function("accessible_paper_rolls", |b| {
+        b.iter(|| {
+            AccessibilityScan::new(&grid)
+                .accessible_paper_rolls()
+                .count()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    peel,
+    peel_dense,
+    peel_by_density,
+    accessibility_scan,
+    dense_accessibility_check
+);
+criterion_main!(benches);
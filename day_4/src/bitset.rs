@@ -0,0 +1,275 @@
+use std::ops::Range;
+
+use crate::{Adjacency, Grid, GridMode, GridStore, GridType};
+
+/// Grid keeping one bit per cell, 64 columns per word, so the peel hot loop tests and counts
+/// neighbors with a few word operations
+pub struct BitGrid {
+    n_rows: usize,
+    n_columns: usize,
+    words_per_row: usize,
+    paper_rolls: Vec<u64>,
+    walls: Vec<u64>,
+    mode: GridMode,
+}
+
+impl BitGrid {
+    const WORD_BITS: usize = u64::BITS as usize;
+
+    /// Word holding the given cell and the cell position within it
+    fn word_position(&self, row: usize, col: usize) -> (usize, u32) {
+        (
+            row * self.words_per_row + col / Self::WORD_BITS,
+            (col % Self::WORD_BITS) as u32,
+        )
+    }
+
+    fn occupied_word(&self, index: usize) -> u64 {
+        self.paper_rolls[index] | self.walls[index]
+    }
+
+    /// Occupied cells among the `len` (1 to 3) cells starting at `first_col`, fetching the
+    /// next word too when they don't fit in the first one
+    fn count_occupied_in_row(&self, row: usize, first_col: usize, len: usize) -> usize {
+        let (index, shift) = self.word_position(row, first_col);
+
+        let mut bits = self.occupied_word(index) >> shift;
+
+        if shift as usize + len > Self::WORD_BITS {
+            bits |= self.occupied_word(index + 1) << (Self::WORD_BITS - shift as usize);
+        }
+
+        (bits & ((1 << len) - 1)).count_ones() as usize
+    }
+}
+
+impl GridStore for BitGrid {
+    fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    fn n_columns(&self) -> usize {
+        self.n_columns
+    }
+
+    fn mode(&self) -> GridMode {
+        self.mode
+    }
+
+    fn is_paper_roll(&self, row: usize, col: usize) -> bool {
+        let (index, shift) = self.word_position(row, col);
+
+        self.paper_rolls[index] >> shift & 1 == 1
+    }
+
+    fn is_occupied(&self, row: usize, col: usize) -> bool {
+        let (index, shift) = self.word_position(row, col);
+
+        self.occupied_word(index) >> shift & 1 == 1
+    }
+
+    fn occupied_neighbor_count(&self, row: usize, col: usize, adjacency: Adjacency) -> usize {
+        // Wrapped positions and orthogonal neighbors go cell by cell
+        if self.mode == GridMode::Toroidal || adjacency == Adjacency::Four {
+            return self
+                .neighbors(row, col, adjacency)
+                .filter(|&(row, col)| self.is_occupied(row, col))
+                .count();
+        }
+
+        let first_col = col.saturating_sub(1);
+        let len = (col + 1).min(self.n_columns - 1) - first_col + 1;
+
+        let rows = row.saturating_sub(1)..(row + 2).min(self.n_rows);
+        let occupied_cells: usize = rows
+            .map(|row| self.count_occupied_in_row(row, first_col, len))
+            .sum();
+
+        // The cell itself is in the middle row
+        occupied_cells - self.is_occupied(row, col) as usize
+    }
+
+    /// The word operations count the whole neighborhood at once, so there's nothing to skip
+    fn count_adjacent_occupied_up_to(
+        &self,
+        row: usize,
+        col: usize,
+        adjacency: Adjacency,
+        limit: usize,
+    ) -> usize {
+        self.occupied_neighbor_count(row, col, adjacency).min(limit)
+    }
+
+    fn remove_item(&mut self, row: usize, col: usize) -> GridType {
+        if row >= self.n_rows || col >= self.n_columns {
+            return GridType::Empty;
+        }
+
+        let (index, shift) = self.word_position(row, col);
+
+        if self.walls[index] >> shift & 1 == 1 {
+            return GridType::Wall;
+        }
+
+        if self.paper_rolls[index] >> shift & 1 == 0 {
+            return GridType::Empty;
+        }
+
+        self.paper_rolls[index] &= !(1 << shift);
+
+        GridType::PaperRoll
+    }
+
+    fn paper_rolls_in_rows(&self, rows: Range<usize>) -> impl Iterator<Item = (usize, usize)> {
+        rows.flat_map(move |row| {
+            (0..self.words_per_row).flat_map(move |word_index| {
+                let mut word = self.paper_rolls[row * self.words_per_row + word_index];
+
+                // Lowest set bit first, which keeps the row-major order
+                std::iter::from_fn(move || {
+                    if word == 0 {
+                        return None;
+                    }
+
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+
+                    Some((row, word_index * Self::WORD_BITS + bit))
+                })
+            })
+        })
+    }
+
+    fn count_paper_rolls(&self) -> usize {
+        self.paper_rolls
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+impl From<&Grid> for BitGrid {
+    fn from(grid: &Grid) -> Self {
+        let words_per_row = grid.n_columns().div_ceil(Self::WORD_BITS);
+
+        let mut bit_grid = Self {
+            n_rows: grid.n_rows(),
+            n_columns: grid.n_columns(),
+            words_per_row,
+            paper_rolls: vec![0; grid.n_rows() * words_per_row],
+            walls: vec![0; grid.n_rows() * words_per_row],
+            mode: grid.mode(),
+        };
+
+        for (row, col) in grid.cells_of_type(GridType::PaperRoll) {
+            let (index, shift) = bit_grid.word_position(row, col);
+            bit_grid.paper_rolls[index] |= 1 << shift;
+        }
+
+        for (row, col) in grid.cells_of_type(GridType::Wall) {
+            let (index, shift) = bit_grid.word_position(row, col);
+            bit_grid.walls[index] |= 1 << shift;
+        }
+
+        bit_grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccessibilityScan, ForkLiftsHelper, generator::random_grid};
+
+    const EXAMPLE_DATA: &str = r#"
+        ..@@.@@@@.
+        @@@.@.@.@@
+        @@@@@.@.@@
+        @.@@@@..@.
+        @@.@@@@.@@
+        .@@@@@@@.@
+        .@.@.@.@@@
+        @.@@@.@@@@
+        .@@@@@@@@.
+        @.@.@@@.@."#;
+
+    #[test]
+    fn test_same_answers_as_dense_grid() {
+        let mut grid = Grid::from(EXAMPLE_DATA);
+        let mut bit_grid = BitGrid::from(&grid);
+
+        assert_eq!(bit_grid.count_paper_rolls(), 71);
+        assert_eq!(
+            bit_grid.paper_rolls().collect::<Vec<(usize, usize)>>(),
+            grid.paper_rolls().collect::<Vec<(usize, usize)>>()
+        );
+        assert_eq!(
+            AccessibilityScan::new(&bit_grid)
+                .find_accessible_paper_rolls()
+                .len(),
+            13
+        );
+
+        // Negative and past the end positions are out of the grid as well
+        for (row, col) in [(-1, 0), (0, -1), (0, 0), (9, 9), (10, 0), (0, 10)] {
+            assert_eq!(
+                bit_grid.get_signed(row, col),
+                grid.get_signed(row, col).copied()
+            );
+        }
+
+        assert_eq!(
+            ForkLiftsHelper::new(&mut bit_grid).run_with_history(),
+            ForkLiftsHelper::new(&mut grid).run_with_history()
+        );
+        assert_eq!(bit_grid.count_paper_rolls(), 28);
+    }
+
+    #[test]
+    fn test_same_history_on_random_grids() {
+        // Wide enough for the neighbors of some cells to span two words
+        for seed in 0..10 {
+            let data = random_grid(40, 150, 40 + seed * 5, seed);
+
+            for mode in [GridMode::Bounded, GridMode::Toroidal] {
+                for adjacency in [Adjacency::Eight, Adjacency::Four] {
+                    let mut grid = Grid::from(data.as_str()).with_mode(mode);
+                    let mut bit_grid = BitGrid::from(&grid);
+
+                    assert_eq!(
+                        ForkLiftsHelper::new(&mut bit_grid)
+                            .with_adjacency(adjacency)
+                            .run_with_history(),
+                        ForkLiftsHelper::new(&mut grid)
+                            .with_adjacency(adjacency)
+                            .run_with_history()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbors_across_words() {
+        let data = format!("{}\n{}", "@".repeat(130), "@".repeat(130));
+        let grid = Grid::from(data.as_str()).with_mode(GridMode::Bounded);
+        let bit_grid = BitGrid::from(&grid);
+
+        for col in [0, 1, 62, 63, 64, 65, 127, 128, 129] {
+            assert_eq!(
+                bit_grid.occupied_neighbor_count(0, col, Adjacency::Eight),
+                grid.occupied_neighbor_count(0, col, Adjacency::Eight)
+            );
+        }
+
+        let grid = Grid::from("@@@#\n@##@\n@@@@");
+        let mut bit_grid = BitGrid::from(&grid);
+
+        assert!(bit_grid.is_occupied(0, 3));
+        assert!(!bit_grid.is_paper_roll(0, 3));
+        assert_eq!(bit_grid.remove_item(1, 1), GridType::Wall);
+        assert_eq!(bit_grid.remove_item(3, 0), GridType::Empty);
+        assert_eq!(bit_grid.remove_item(0, 0), GridType::PaperRoll);
+        assert_eq!(bit_grid.remove_item(0, 0), GridType::Empty);
+        assert_eq!(bit_grid.occupied_neighbor_count(1, 1, Adjacency::Eight), 7);
+    }
+}
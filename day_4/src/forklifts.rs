@@ -0,0 +1,2448 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt, fs, io,
+    path::Path,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    Adjacency, BoundaryRule, Grid, GridMode, GridParseError, GridStore, GridType, ParallelStore,
+    Pos, RemovedKind, WallRule,
+};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeelOutcome {
+    pub removed: usize,
+    pub rounds: usize,
+    pub remaining: usize,
+    /// Removed rolls of every round, in order
+    pub removed_per_round: Vec<usize>,
+}
+
+impl PeelOutcome {
+    /// Summary of a peel from its history and the paper rolls left afterwards
+    pub fn from_history(history: &[Vec<Pos>], remaining: usize) -> Self {
+        let removed_per_round: Vec<usize> = history.iter().map(|round| round.len()).collect();
+
+        Self {
+            removed: removed_per_round.iter().sum(),
+            rounds: history.len(),
+            remaining,
+            removed_per_round,
+        }
+    }
+
+    /// 0-based index and removed rolls of the round removing the most, the earliest one on ties
+    pub fn peak_round(&self) -> Option<(usize, usize)> {
+        self.removed_per_round
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by_key(|&(round_index, removed)| (Reverse(removed), round_index))
+    }
+
+    /// 0-based index and removed rolls of the round removing the least, the earliest one on ties
+    pub fn min_round(&self) -> Option<(usize, usize)> {
+        self.removed_per_round
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by_key(|&(_, removed)| removed)
+    }
+
+    pub fn mean_removed_per_round(&self) -> Option<f64> {
+        (self.rounds > 0).then(|| self.removed as f64 / self.rounds as f64)
+    }
+}
+
+/// Progress of the peel after a single round
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundStats {
+    /// Starting at 1
+    pub round: usize,
+    pub removed: usize,
+    pub remaining: usize,
+}
+
+/// Rounds and removed rolls so far, carried across checkpoints
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeelProgress {
+    pub rounds: usize,
+    pub removed: usize,
+}
+
+/// Everything a checkpoint keeps besides the grid, whose mode is restored on the grid itself
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct PeelCheckpoint {
+    pub progress: PeelProgress,
+    pub threshold: usize,
+    pub adjacency: Adjacency,
+    pub boundary_rule: BoundaryRule,
+    pub wall_rule: WallRule,
+}
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    /// First line isn't a checkpoint magic line, or it's from an unknown version
+    UnsupportedFormat {
+        line: String,
+    },
+    InvalidHeader {
+        line: String,
+    },
+    InvalidGrid(GridParseError),
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to access the checkpoint: {}", error),
+            Self::UnsupportedFormat { line } => {
+                write!(f, "Unsupported checkpoint format '{}'", line)
+            }
+            Self::InvalidHeader { line } => write!(f, "Invalid checkpoint header '{}'", line),
+            Self::InvalidGrid(error) => write!(f, "Invalid checkpoint grid: {}", error),
+        }
+    }
+}
+
+impl Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::InvalidGrid(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Read-only accessibility analysis, only borrowing the grid immutably so several of them can
+/// look at the same grid at once
+pub struct AccessibilityScan<'a, S: GridStore = Grid> {
+    grid: &'a S,
+    threshold: usize,
+    adjacency: Adjacency,
+    boundary_rule: BoundaryRule,
+    wall_rule: WallRule,
+    neighbor_counts: Option<&'a [Vec<u8>]>,
+}
+
+impl<'a, S: GridStore> AccessibilityScan<'a, S> {
+    const DEFAULT_THRESHOLD: usize = 4;
+
+    /// Below this many cells the threads overhead isn't worth it
+    #[cfg(feature = "parallel")]
+    const PARALLEL_MIN_CELLS: usize = 250_000;
+
+    pub fn new(grid: &'a S) -> Self {
+        Self::new_with_threshold(grid, Self::DEFAULT_THRESHOLD)
+    }
+
+    /// Paper rolls are accessible with less than `threshold` adjacent rolls, so 0 makes none
+    /// accessible and 9 makes all of them
+    pub fn new_with_threshold(grid: &'a S, threshold: usize) -> Self {
+        Self {
+            grid,
+            threshold,
+            adjacency: Adjacency::Eight,
+            boundary_rule: BoundaryRule::default(),
+            wall_rule: WallRule::default(),
+            neighbor_counts: None,
+        }
+    }
+
+    /// Neighbors taken into account, the threshold is compared against the occupied ones among
+    /// them so anything above 4 makes every roll accessible with `Adjacency::Four`
+    pub fn with_adjacency(mut self, adjacency: Adjacency) -> Self {
+        self.adjacency = adjacency;
+        self
+    }
+
+    pub fn with_boundary_rule(mut self, boundary_rule: BoundaryRule) -> Self {
+        self.boundary_rule = boundary_rule;
+        self
+    }
+
+    pub fn with_wall_rule(mut self, wall_rule: WallRule) -> Self {
+        self.wall_rule = wall_rule;
+        self
+    }
+
+    /// Take the paper rolls counts from a map computed once with `Grid::compute_neighbor_count_map`
+    /// instead of scanning the neighbors on every check. The map only holds the eight neighbors
+    /// counts, so it's ignored with `Adjacency::Four`. Walls aren't in the map, they're still
+    /// looked up with `WallRule::WallIsOccupied`
+    pub fn with_neighbor_count_map(mut self, neighbor_counts: &'a [Vec<u8>]) -> Self {
+        assert!(
+            neighbor_counts.len() == self.grid.n_rows()
+                && neighbor_counts
+                    .iter()
+                    .all(|row| row.len() == self.grid.n_columns()),
+            "Neighbor count map doesn't match the grid dimensions"
+        );
+
+        self.neighbor_counts = Some(neighbor_counts);
+        self
+    }
+
+    /// Occupied neighbor slots of the given position, compared against the threshold
+    fn count_occupied_neighbors(&self, row: usize, col: usize) -> usize {
+        self.count_occupied_outside(row, col) + self.count_occupied_inside(row, col, usize::MAX)
+    }
+
+    /// Occupied neighbors within the grid, stopping once `limit` of them are found
+    fn count_occupied_inside(&self, row: usize, col: usize, limit: usize) -> usize {
+        match (self.neighbor_counts, self.wall_rule) {
+            (Some(neighbor_counts), wall_rule) if self.adjacency == Adjacency::Eight => {
+                let walls = match wall_rule {
+                    WallRule::WallIsOccupied => {
+                        self.grid.count_adjacent_walls(row, col, self.adjacency)
+                    }
+                    WallRule::WallIsEmpty => 0,
+                };
+
+                neighbor_counts[row][col] as usize + walls
+            }
+            (_, WallRule::WallIsEmpty) => {
+                self.grid
+                    .count_adjacent_paper_rolls_up_to(row, col, self.adjacency, limit)
+            }
+            // Nothing to stop early for when counting all of them
+            (_, WallRule::WallIsOccupied) if limit == usize::MAX => {
+                self.grid.occupied_neighbor_count(row, col, self.adjacency)
+            }
+            (_, WallRule::WallIsOccupied) => {
+                self.grid
+                    .count_adjacent_occupied_up_to(row, col, self.adjacency, limit)
+            }
+        }
+    }
+
+    fn count_occupied_outside(&self, row: usize, col: usize) -> usize {
+        match self.boundary_rule {
+            BoundaryRule::OutsideIsEmpty => 0,
+            BoundaryRule::OutsideIsOccupied => self
+                .adjacency
+                .offsets()
+                .iter()
+                .filter(|&&[row_offset, col_offset]| {
+                    self.grid
+                        .get_signed(row as isize + row_offset, col as isize + col_offset)
+                        .is_none()
+                })
+                .count(),
+        }
+    }
+
+    fn is_item_accessible(&self, row: usize, col: usize) -> bool {
+        if !self.grid.is_paper_roll(row, col) {
+            return false;
+        }
+
+        // The check is decided once the threshold is reached, no need to count further
+        match self
+            .threshold
+            .checked_sub(self.count_occupied_outside(row, col))
+        {
+            Some(limit) if limit > 0 => self.count_occupied_inside(row, col, limit) < limit,
+            _ => false,
+        }
+    }
+
+    /// Lazily walk the grid in row-major order without allocating
+    pub fn accessible_paper_rolls(self) -> impl Iterator<Item = Pos> {
+        self.grid
+            .paper_rolls()
+            .filter(move |&(row, col)| self.is_item_accessible(row, col))
+            .map(Pos::from)
+    }
+
+    /// Accessible paper rolls in each row, from top to bottom. Use
+    /// `accessible_per_row_and_column` when the columns are needed too
+    pub fn accessible_per_row(self) -> Vec<usize> {
+        self.accessible_per_row_and_column().0
+    }
+
+    /// Accessible paper rolls in each column, from left to right. Use
+    /// `accessible_per_row_and_column` when the rows are needed too
+    pub fn accessible_per_column(self) -> Vec<usize> {
+        self.accessible_per_row_and_column().1
+    }
+
+    /// Accessible paper rolls in each row and in each column, both from a single scan
+    pub fn accessible_per_row_and_column(self) -> (Vec<usize>, Vec<usize>) {
+        let mut per_row = vec![0; self.grid.n_rows()];
+        let mut per_column = vec![0; self.grid.n_columns()];
+
+        for pos in self.accessible_paper_rolls() {
+            per_row[pos.row] += 1;
+            per_column[pos.col] += 1;
+        }
+
+        // Every accessible roll is in exactly one row and one column, checked against a
+        // separate scan
+        #[cfg(debug_assertions)]
+        {
+            let total = self.accessible_paper_rolls().count();
+
+            debug_assert_eq!(per_row.iter().sum::<usize>(), total);
+            debug_assert_eq!(per_column.iter().sum::<usize>(), total);
+        }
+
+        (per_row, per_column)
+    }
+
+    /// Scanned in parallel row bands on large grids when the `parallel` feature is enabled,
+    /// keeping the same row-major order
+    pub fn find_accessible_paper_rolls(self) -> std::vec::IntoIter<Pos>
+    where
+        S: ParallelStore,
+    {
+        #[cfg(feature = "parallel")]
+        if self.grid.n_rows() * self.grid.n_columns() >= Self::PARALLEL_MIN_CELLS {
+            return self.find_accessible_paper_rolls_parallel().into_iter();
+        }
+
+        self.accessible_paper_rolls()
+            .collect::<Vec<Pos>>()
+            .into_iter()
+    }
+
+    #[cfg(feature = "parallel")]
+    fn find_accessible_paper_rolls_parallel(self) -> Vec<Pos>
+    where
+        S: ParallelStore,
+    {
+        let n_rows = self.grid.n_rows();
+
+        // A few bands per thread so uneven bands still balance out
+        let band_size = n_rows.div_ceil(rayon::current_num_threads() * 4).max(1);
+
+        (0..n_rows.div_ceil(band_size))
+            .into_par_iter()
+            .map(|band| {
+                let rows = band * band_size..((band + 1) * band_size).min(n_rows);
+
+                self.grid
+                    .paper_rolls_in_rows(rows)
+                    .filter(|&(row, col)| self.is_item_accessible(row, col))
+                    .map(Pos::from)
+                    .collect::<Vec<Pos>>()
+            })
+            .collect::<Vec<Vec<Pos>>>()
+            .concat()
+    }
+}
+
+// Derived ones would require the grid itself to be `Copy`
+impl<S: GridStore> Clone for AccessibilityScan<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: GridStore> Copy for AccessibilityScan<'_, S> {}
+
+/// Removes the accessible paper rolls, borrowing the grid mutably
+pub struct ForkLiftsHelper<'a, S: GridStore = Grid> {
+    grid: &'a mut S,
+    threshold: usize,
+    adjacency: Adjacency,
+    boundary_rule: BoundaryRule,
+    wall_rule: WallRule,
+    progress: PeelProgress,
+}
+
+impl<'a, S: GridStore> ForkLiftsHelper<'a, S> {
+    pub fn new(grid: &'a mut S) -> Self {
+        Self::new_with_threshold(grid, AccessibilityScan::<S>::DEFAULT_THRESHOLD)
+    }
+
+    /// Same threshold semantics as `AccessibilityScan::new_with_threshold`
+    pub fn new_with_threshold(grid: &'a mut S, threshold: usize) -> Self {
+        Self {
+            grid,
+            threshold,
+            adjacency: Adjacency::Eight,
+            boundary_rule: BoundaryRule::default(),
+            wall_rule: WallRule::default(),
+            progress: PeelProgress::default(),
+        }
+    }
+
+    pub fn with_adjacency(mut self, adjacency: Adjacency) -> Self {
+        self.adjacency = adjacency;
+        self
+    }
+
+    /// Only changes the neighbor counts, the cells outside are never removed
+    pub fn with_boundary_rule(mut self, boundary_rule: BoundaryRule) -> Self {
+        self.boundary_rule = boundary_rule;
+        self
+    }
+
+    /// Only changes the neighbor counts, walls are never removed either way
+    pub fn with_wall_rule(mut self, wall_rule: WallRule) -> Self {
+        self.wall_rule = wall_rule;
+        self
+    }
+
+    /// Keep counting from a previous peel of the same grid, like one loaded from a checkpoint
+    pub fn with_progress(mut self, progress: PeelProgress) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Rounds and removed rolls of every peel run by this helper, plus the starting progress
+    pub fn progress(&self) -> PeelProgress {
+        self.progress
+    }
+
+    /// Paper rolls with at most `max_neighbors` adjacent rolls are accessible, the default being 3
+    pub fn with_threshold(grid: &'a mut S, max_neighbors: u8) -> Self {
+        Self::new_with_threshold(grid, max_neighbors as usize + 1)
+    }
+
+    /// Analysis of the grid in its current state, with the same threshold, adjacency and rules
+    pub fn scan(&self) -> AccessibilityScan<'_, S> {
+        AccessibilityScan::new_with_threshold(self.grid, self.threshold)
+            .with_adjacency(self.adjacency)
+            .with_boundary_rule(self.boundary_rule)
+            .with_wall_rule(self.wall_rule)
+    }
+
+    pub fn iterative_remove_accessible_paper_rolls(&mut self) -> usize
+    where
+        S: ParallelStore,
+    {
+        #[cfg(debug_assertions)]
+        let initial_paper_rolls = self.grid.count_paper_rolls();
+
+        let peel_outcome = self.run_to_completion();
+
+        // Every paper roll is either removed or still in the grid
+        #[cfg(debug_assertions)]
+        debug_assert_eq!(
+            initial_paper_rolls,
+            peel_outcome.removed + peel_outcome.remaining
+        );
+
+        peel_outcome.removed
+    }
+
+    /// Remove accessible paper rolls round by round until none is accessible
+    pub fn run_to_completion(&mut self) -> PeelOutcome
+    where
+        S: ParallelStore,
+    {
+        let history = self.run_with_history();
+
+        PeelOutcome::from_history(&history, self.grid.count_paper_rolls())
+    }
+
+    /// Removed positions of every round, in row-major order within each round
+    pub fn run_with_history(&mut self) -> Vec<Vec<Pos>>
+    where
+        S: ParallelStore,
+    {
+        self.run_rounds(usize::MAX)
+    }
+
+    /// Same as `run_with_history`, but stopping after `n_rounds` rounds so the peel can be
+    /// resumed later. The neighbors counts are rebuilt on every call, so the grid can be freely
+    /// modified or restored in between
+    pub fn run_rounds(&mut self, n_rounds: usize) -> Vec<Vec<Pos>>
+    where
+        S: ParallelStore,
+    {
+        if n_rounds == 0 {
+            return Vec::new();
+        }
+
+        self.peel_rounds()
+            .take(n_rounds)
+            .map(|(removed, _)| removed)
+            .collect()
+    }
+
+    /// Lazy peel yielding the stats of every round as it's removed, so taking a few of them
+    /// only removes those rounds. Like `run_rounds` it can be resumed, counting rounds from 1 again
+    pub fn rounds(&mut self) -> impl Iterator<Item = RoundStats>
+    where
+        S: ParallelStore,
+    {
+        self.peel_rounds()
+            .enumerate()
+            .map(|(round_index, (removed, remaining))| RoundStats {
+                round: round_index + 1,
+                removed: removed.len(),
+                remaining,
+            })
+    }
+
+    fn peel_rounds(&mut self) -> PeelRounds<'_, S>
+    where
+        S: ParallelStore,
+    {
+        let scan = self.scan();
+
+        let occupied_neighbors = self
+            .grid
+            .paper_rolls()
+            .map(|(row, col)| (Pos::new(row, col), scan.count_occupied_neighbors(row, col)))
+            .collect();
+        let current_round: Vec<Pos> = scan.find_accessible_paper_rolls().collect();
+
+        PeelRounds {
+            grid: self.grid,
+            progress: &mut self.progress,
+            adjacency: self.adjacency,
+            threshold: self.threshold,
+            occupied_neighbors,
+            current_round,
+        }
+    }
+
+    /// Same history as `run_with_history`, keeping the neighbors counts in a flat array
+    /// indexed by cell instead of a map, so the whole peel costs one pass over the grid plus
+    /// a neighbors update per removal. The array has a slot per cell, which makes it a poor
+    /// fit for huge sparse grids
+    pub fn run_queue_based(&mut self) -> Vec<Vec<Pos>> {
+        let n_columns = self.grid.n_columns();
+        let mut occupied_neighbors = vec![0u8; self.grid.n_rows() * n_columns];
+
+        // Rolls come in row-major order, so the first round is already sorted
+        let mut current_round = Vec::new();
+        let scan = self.scan();
+
+        for (row, col) in self.grid.paper_rolls() {
+            let count = scan.count_occupied_neighbors(row, col);
+            occupied_neighbors[row * n_columns + col] = count as u8;
+
+            if count < self.threshold {
+                current_round.push(Pos::new(row, col));
+            }
+        }
+
+        let mut history = Vec::new();
+
+        while !current_round.is_empty() {
+            // Same round boundaries as `run_rounds`, the whole round goes before any update
+            for &pos in current_round.iter() {
+                let removed_kind = self.grid.try_remove_item(pos.row, pos.col);
+
+                debug_assert_eq!(
+                    removed_kind,
+                    Ok(RemovedKind::PaperRoll),
+                    "Paper roll at {} removed twice",
+                    pos
+                );
+            }
+
+            let mut next_round = Vec::new();
+
+            for &pos in current_round.iter() {
+                for (row, col) in self.grid.neighbors(pos.row, pos.col, self.adjacency) {
+                    if !self.grid.is_paper_roll(row, col) {
+                        continue;
+                    }
+
+                    let count = &mut occupied_neighbors[row * n_columns + col];
+
+                    if *count as usize == self.threshold {
+                        next_round.push(Pos::new(row, col));
+                    }
+
+                    *count -= 1;
+                }
+            }
+
+            next_round.sort_unstable();
+            history.push(std::mem::replace(&mut current_round, next_round));
+        }
+
+        self.progress.rounds += history.len();
+        self.progress.removed += history.iter().map(|round| round.len()).sum::<usize>();
+
+        history
+    }
+
+    /// Same history as `run_with_history`, computing every round from the current cells into
+    /// a second buffer and swapping both afterwards. Reading only the previous round's cells
+    /// lets rows be decided independently, in parallel with the `parallel` feature. The
+    /// trade-off is memory and work: both buffers keep a byte per cell, doubling the grid
+    /// size, and every round visits the whole grid, so it only pays off on dense grids
+    pub fn run_double_buffered(&mut self) -> Vec<Vec<Pos>>
+    where
+        S: ParallelStore,
+    {
+        let mut current = self.buffer_cells();
+        let mut next = current.clone();
+
+        let mut history = Vec::new();
+
+        loop {
+            let removed = self.double_buffered_round(&current, &mut next);
+
+            if removed.is_empty() {
+                break;
+            }
+
+            for &pos in removed.iter() {
+                let removed_kind = self.grid.try_remove_item(pos.row, pos.col);
+
+                debug_assert_eq!(
+                    removed_kind,
+                    Ok(RemovedKind::PaperRoll),
+                    "Paper roll at {} removed twice",
+                    pos
+                );
+            }
+
+            std::mem::swap(&mut current, &mut next);
+            history.push(removed);
+        }
+
+        self.progress.rounds += history.len();
+        self.progress.removed += history.iter().map(|round| round.len()).sum::<usize>();
+
+        history
+    }
+
+    /// Grid cells in row-major order, as used by `run_double_buffered`
+    fn buffer_cells(&self) -> Vec<BufferCell> {
+        let (n_rows, n_columns) = (self.grid.n_rows(), self.grid.n_columns());
+
+        (0..n_rows)
+            .flat_map(|row| (0..n_columns).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                match (
+                    self.grid.is_paper_roll(row, col),
+                    self.grid.is_occupied(row, col),
+                ) {
+                    (true, _) => BufferCell::PaperRoll,
+                    (false, true) => BufferCell::Wall,
+                    (false, false) => BufferCell::Empty,
+                }
+            })
+            .collect()
+    }
+
+    /// Write the cells after one round into `next`, returning the removed rolls in row-major
+    /// order
+    fn double_buffered_round(&self, current: &[BufferCell], next: &mut [BufferCell]) -> Vec<Pos>
+    where
+        S: ParallelStore,
+    {
+        #[cfg(feature = "parallel")]
+        if current.len() >= AccessibilityScan::<S>::PARALLEL_MIN_CELLS {
+            return self.double_buffered_round_parallel(current, next);
+        }
+
+        // Chunks can't be empty, but there are no cells to split with zero columns anyway
+        next.chunks_mut(self.grid.n_columns().max(1))
+            .enumerate()
+            .flat_map(|(row, next_row)| self.double_buffered_row(current, row, next_row))
+            .collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    fn double_buffered_round_parallel(
+        &self,
+        current: &[BufferCell],
+        next: &mut [BufferCell],
+    ) -> Vec<Pos>
+    where
+        S: ParallelStore,
+    {
+        next.par_chunks_mut(self.grid.n_columns().max(1))
+            .enumerate()
+            .map(|(row, next_row)| self.double_buffered_row(current, row, next_row))
+            .collect::<Vec<Vec<Pos>>>()
+            .concat()
+    }
+
+    fn double_buffered_row(
+        &self,
+        current: &[BufferCell],
+        row: usize,
+        next_row: &mut [BufferCell],
+    ) -> Vec<Pos> {
+        let n_columns = next_row.len();
+        let scan = self.scan();
+        let mut removed = Vec::new();
+
+        for (col, next_cell) in next_row.iter_mut().enumerate() {
+            *next_cell = current[row * n_columns + col];
+
+            if *next_cell != BufferCell::PaperRoll {
+                continue;
+            }
+
+            let occupied_inside = self
+                .grid
+                .neighbors(row, col, self.adjacency)
+                .filter(|&(row, col)| match current[row * n_columns + col] {
+                    BufferCell::PaperRoll => true,
+                    BufferCell::Wall => self.wall_rule == WallRule::WallIsOccupied,
+                    BufferCell::Empty => false,
+                })
+                .count();
+
+            if scan.count_occupied_outside(row, col) + occupied_inside < self.threshold {
+                *next_cell = BufferCell::Empty;
+                removed.push(Pos::new(row, col));
+            }
+        }
+
+        removed
+    }
+}
+
+/// Cell of the `run_double_buffered` buffers, a byte each
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum BufferCell {
+    Empty,
+    PaperRoll,
+    Wall,
+}
+
+impl<'a> ForkLiftsHelper<'a, Grid> {
+    const CHECKPOINT_MAGIC: &'static str = "day_4 peel checkpoint v2";
+
+    /// Write the progress, the peel settings and the grid as plain text: the magic line, a
+    /// `rounds=<n> removed=<n> mode=<mode> adjacency=<4|8> threshold=<n> boundary=<rule>
+    /// walls=<rule>` header and the rendered grid. Only the cells are kept, with removed
+    /// markers saved as empty cells
+    pub fn save_checkpoint(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let grid = self.grid.render_with(|cell| match cell {
+            GridType::Removed => char::from(&GridType::Empty),
+            cell => char::from(cell),
+        });
+
+        let mode = match self.grid.mode() {
+            GridMode::Bounded => "bounded",
+            GridMode::Toroidal => "toroidal",
+        };
+        let adjacency = match self.adjacency {
+            Adjacency::Eight => 8,
+            Adjacency::Four => 4,
+        };
+        let boundary_rule = match self.boundary_rule {
+            BoundaryRule::OutsideIsEmpty => "empty",
+            BoundaryRule::OutsideIsOccupied => "occupied",
+        };
+        let wall_rule = match self.wall_rule {
+            WallRule::WallIsOccupied => "occupied",
+            WallRule::WallIsEmpty => "empty",
+        };
+
+        let checkpoint = format!(
+            "{}\nrounds={} removed={} mode={} adjacency={} threshold={} boundary={} walls={}\n{}\n",
+            Self::CHECKPOINT_MAGIC,
+            self.progress.rounds,
+            self.progress.removed,
+            mode,
+            adjacency,
+            self.threshold,
+            boundary_rule,
+            wall_rule,
+            grid
+        );
+
+        fs::write(path, checkpoint).map_err(CheckpointError::Io)
+    }
+
+    /// Grid and peel state saved by `save_checkpoint`, to resume with `from_checkpoint`
+    pub fn load_checkpoint(
+        path: impl AsRef<Path>,
+    ) -> Result<(Grid, PeelCheckpoint), CheckpointError> {
+        let checkpoint = fs::read_to_string(path).map_err(CheckpointError::Io)?;
+        let mut lines = checkpoint.splitn(3, '\n');
+
+        let magic = lines.next().unwrap_or_default();
+
+        if magic != Self::CHECKPOINT_MAGIC {
+            return Err(CheckpointError::UnsupportedFormat {
+                line: magic.to_string(),
+            });
+        }
+
+        let header = lines.next().unwrap_or_default();
+        let (mode, peel_checkpoint) = Self::parse_checkpoint_header(header).ok_or_else(|| {
+            CheckpointError::InvalidHeader {
+                line: header.to_string(),
+            }
+        })?;
+
+        let grid = lines
+            .next()
+            .unwrap_or_default()
+            .parse::<Grid>()
+            .map_err(CheckpointError::InvalidGrid)?;
+
+        Ok((grid.with_mode(mode), peel_checkpoint))
+    }
+
+    /// Helper resuming the peel of a loaded checkpoint, with its settings and progress
+    pub fn from_checkpoint(grid: &'a mut Grid, checkpoint: PeelCheckpoint) -> Self {
+        Self::new_with_threshold(grid, checkpoint.threshold)
+            .with_adjacency(checkpoint.adjacency)
+            .with_boundary_rule(checkpoint.boundary_rule)
+            .with_wall_rule(checkpoint.wall_rule)
+            .with_progress(checkpoint.progress)
+    }
+
+    fn parse_checkpoint_header(header: &str) -> Option<(GridMode, PeelCheckpoint)> {
+        let mut fields = header.split(' ');
+        let mut field = |name: &str| fields.next()?.strip_prefix(name)?.strip_prefix('=');
+
+        let rounds = field("rounds")?.parse().ok()?;
+        let removed = field("removed")?.parse().ok()?;
+        let mode = match field("mode")? {
+            "bounded" => GridMode::Bounded,
+            "toroidal" => GridMode::Toroidal,
+            _ => return None,
+        };
+        let adjacency = match field("adjacency")? {
+            "8" => Adjacency::Eight,
+            "4" => Adjacency::Four,
+            _ => return None,
+        };
+        let threshold = field("threshold")?.parse().ok()?;
+        let boundary_rule = match field("boundary")? {
+            "empty" => BoundaryRule::OutsideIsEmpty,
+            "occupied" => BoundaryRule::OutsideIsOccupied,
+            _ => return None,
+        };
+        let wall_rule = match field("walls")? {
+            "occupied" => WallRule::WallIsOccupied,
+            "empty" => WallRule::WallIsEmpty,
+            _ => return None,
+        };
+
+        // Nothing else is expected after the known fields
+        if fields.next().is_some() {
+            return None;
+        }
+
+        Some((
+            mode,
+            PeelCheckpoint {
+                progress: PeelProgress { rounds, removed },
+                threshold,
+                adjacency,
+                boundary_rule,
+                wall_rule,
+            },
+        ))
+    }
+}
+
+/// Incremental peel removing one round per step. Only the neighbors of removed rolls can
+/// become accessible, so instead of rescanning the grid every round it keeps the occupied
+/// neighbors count of every remaining roll
+struct PeelRounds<'a, S: GridStore> {
+    grid: &'a mut S,
+    progress: &'a mut PeelProgress,
+    adjacency: Adjacency,
+    threshold: usize,
+    occupied_neighbors: HashMap<Pos, usize>,
+    current_round: Vec<Pos>,
+}
+
+impl<S: GridStore> Iterator for PeelRounds<'_, S> {
+    /// Removed positions of the round and the paper rolls remaining after it
+    type Item = (Vec<Pos>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The final round without accessible rolls isn't a round
+        if self.current_round.is_empty() {
+            return None;
+        }
+
+        // Remove the whole round first, so its rolls aren't picked up for the next one
+        for &pos in self.current_round.iter() {
+            let removed_kind = self.grid.try_remove_item(pos.row, pos.col);
+
+            debug_assert_eq!(
+                removed_kind,
+                Ok(RemovedKind::PaperRoll),
+                "Paper roll at {} removed twice",
+                pos
+            );
+
+            self.occupied_neighbors.remove(&pos);
+        }
+
+        let mut next_round = Vec::new();
+
+        for &pos in self.current_round.iter() {
+            for position in self.grid.neighbors(pos.row, pos.col, self.adjacency) {
+                let position = Pos::from(position);
+
+                if let Some(count) = self.occupied_neighbors.get_mut(&position) {
+                    // Enqueue only when crossing below the threshold, so it happens once
+                    if *count == self.threshold {
+                        next_round.push(position);
+                    }
+
+                    *count -= 1;
+                }
+            }
+        }
+
+        next_round.sort_unstable();
+
+        self.progress.rounds += 1;
+        self.progress.removed += self.current_round.len();
+
+        Some((
+            std::mem::replace(&mut self.current_round, next_round),
+            self.occupied_neighbors.len(),
+        ))
+    }
+}
+
+// Queries running a peel, kept here so the grid doesn't depend on the forklifts
+impl Grid {
+    /// Paper rolls left once the peel with the given threshold and adjacency can't remove any
+    /// more, in row-major order. The peel runs on a copy, leaving this grid untouched
+    pub fn stuck_rolls(&self, threshold: usize, adjacency: Adjacency) -> Vec<(usize, usize)> {
+        let mut grid = self.clone();
+
+        ForkLiftsHelper::new_with_threshold(&mut grid, threshold)
+            .with_adjacency(adjacency)
+            .run_queue_based();
+
+        grid.paper_rolls().collect()
+    }
+
+    /// Whether the default peel of `ForkLiftsHelper::new` removes every paper roll
+    pub fn is_fully_clearable(&self) -> bool {
+        let mut grid = self.clone();
+
+        ForkLiftsHelper::new(&mut grid).run_queue_based();
+
+        grid.count_paper_rolls() == 0
+    }
+}
+
+/// Grid as it was before a peel round, with the rolls removed in that round shown as 'x'
+pub fn render_round(grid_before: &Grid, removed: &[Pos]) -> String {
+    let removed: HashSet<&Pos> = removed.iter().collect();
+
+    grid_before
+        .to_string()
+        .lines()
+        .enumerate()
+        .map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(col, char)| match removed.contains(&Pos::new(row, col)) {
+                    true => 'x',
+                    false => char,
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BitGrid, SparseGrid, generator::random_grid};
+
+    /// Path of a temporary file, removed when dropped so failed asserts don't leave it behind
+    struct TempPath(std::path::PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(format!("{}_{}.txt", name, std::process::id())))
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    /// Rescan the whole grid every round, as a reference for the incremental peel
+    fn rescan_history(grid: &mut impl ParallelStore, threshold: usize) -> Vec<Vec<Pos>> {
+        let mut history = Vec::new();
+
+        loop {
+            let valid_paper_rolls: Vec<Pos> =
+                AccessibilityScan::new_with_threshold(grid, threshold)
+                    .find_accessible_paper_rolls()
+                    .collect();
+
+            if valid_paper_rolls.is_empty() {
+                return history;
+            }
+
+            for pos in valid_paper_rolls.iter() {
+                grid.remove_item(pos.row, pos.col);
+            }
+
+            history.push(valid_paper_rolls);
+        }
+    }
+
+    #[test]
+    fn test_example_input() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+
+        let valid_paper_rolls = forklifts_helper.scan().find_accessible_paper_rolls();
+
+        assert_eq!(valid_paper_rolls.len(), 13);
+        assert_eq!(valid_paper_rolls.count(), 13);
+
+        let removed_paper_rolls = forklifts_helper.iterative_remove_accessible_paper_rolls();
+
+        assert_eq!(removed_paper_rolls, 43);
+    }
+
+    #[test]
+    fn test_lazy_accessible_paper_rolls() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let grid = Grid::from(data);
+        let accessibility_scan = AccessibilityScan::new(&grid);
+
+        // Former implementation, collecting every row before filtering
+        let row_collected_paper_rolls: Vec<Pos> = (0..accessibility_scan.grid.n_rows())
+            .flat_map(|row_index| {
+                (0..accessibility_scan.grid.n_columns())
+                    .map(|col_index| Pos::new(row_index, col_index))
+                    .collect::<Vec<Pos>>()
+            })
+            .filter(|pos| accessibility_scan.is_item_accessible(pos.row, pos.col))
+            .collect();
+
+        let accessible_paper_rolls: Vec<Pos> =
+            accessibility_scan.accessible_paper_rolls().collect();
+
+        assert_eq!(accessible_paper_rolls, row_collected_paper_rolls);
+        assert_eq!(
+            accessibility_scan
+                .find_accessible_paper_rolls()
+                .collect::<Vec<Pos>>(),
+            accessible_paper_rolls
+        );
+        assert_eq!(
+            accessibility_scan.accessible_paper_rolls().next(),
+            Some(Pos::new(0, 2))
+        );
+    }
+
+    #[test]
+    fn test_scan_shared_grid() {
+        let grid = Grid::from("@@@\n@@@\n@@@");
+        let grid_ref = &grid;
+
+        let accessibility_scan = AccessibilityScan::new(&grid);
+        let orthogonal_scan =
+            AccessibilityScan::new_with_threshold(grid_ref, 4).with_adjacency(Adjacency::Four);
+
+        // Both analyses and the other reference are alive at the same time
+        assert_eq!(accessibility_scan.find_accessible_paper_rolls().len(), 4);
+        assert_eq!(orthogonal_scan.accessible_paper_rolls().count(), 8);
+        assert_eq!(grid_ref.count_paper_rolls(), 9);
+        assert_eq!(accessibility_scan.accessible_paper_rolls().count(), 4);
+    }
+
+    #[test]
+    fn test_scan_with_neighbor_count_map() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        for grid in [
+            Grid::from(data),
+            Grid::from(data).with_mode(GridMode::Toroidal),
+            Grid::from("@#@@\n@##@\n@@@@"),
+        ] {
+            let neighbor_counts = grid.compute_neighbor_count_map();
+            let accessibility_scan = AccessibilityScan::new(&grid);
+
+            assert_eq!(
+                accessibility_scan
+                    .with_neighbor_count_map(&neighbor_counts)
+                    .find_accessible_paper_rolls()
+                    .collect::<Vec<Pos>>(),
+                accessibility_scan
+                    .find_accessible_paper_rolls()
+                    .collect::<Vec<Pos>>()
+            );
+
+            let orthogonal_scan = accessibility_scan.with_adjacency(Adjacency::Four);
+
+            assert_eq!(
+                orthogonal_scan
+                    .with_neighbor_count_map(&neighbor_counts)
+                    .accessible_paper_rolls()
+                    .collect::<Vec<Pos>>(),
+                orthogonal_scan
+                    .accessible_paper_rolls()
+                    .collect::<Vec<Pos>>()
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Neighbor count map doesn't match the grid dimensions")]
+    fn test_neighbor_count_map_dimensions_panic() {
+        let grid = Grid::from("@@\n@@");
+        let neighbor_counts = Grid::from("@@@\n@@@").compute_neighbor_count_map();
+
+        let _ = AccessibilityScan::new(&grid).with_neighbor_count_map(&neighbor_counts);
+    }
+
+    #[test]
+    fn test_peel_outcome() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        assert_eq!(grid.count_paper_rolls(), 71);
+
+        let peel_outcome = ForkLiftsHelper::new(&mut grid).run_to_completion();
+
+        assert_eq!(
+            peel_outcome,
+            PeelOutcome {
+                removed: 43,
+                rounds: 9,
+                remaining: 28,
+                removed_per_round: Vec::from([13, 12, 7, 5, 2, 1, 1, 1, 1])
+            }
+        );
+
+        let mut grid = Grid::from("...\n...");
+        let peel_outcome = ForkLiftsHelper::new(&mut grid).run_to_completion();
+
+        assert_eq!(peel_outcome.rounds, 0);
+        assert_eq!(peel_outcome.removed, 0);
+
+        let mut grid = Grid::from("@@@\n@@@\n@@@").with_mode(GridMode::Toroidal);
+        let peel_outcome = ForkLiftsHelper::new(&mut grid).run_to_completion();
+
+        assert_eq!(peel_outcome.rounds, 0);
+        assert_eq!(peel_outcome.remaining, 9);
+    }
+
+    #[test]
+    fn incremental_peel_matches_rescan() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let mut rescanned_grid = grid.clone();
+
+        let history = ForkLiftsHelper::new(&mut grid).run_with_history();
+
+        assert_eq!(history, rescan_history(&mut rescanned_grid, 4));
+        assert_eq!(grid, rescanned_grid);
+        assert_eq!(
+            history
+                .iter()
+                .map(|round| round.len())
+                .collect::<Vec<usize>>(),
+            Vec::from([13, 12, 7, 5, 2, 1, 1, 1, 1])
+        );
+
+        for seed in 0..20 {
+            let data = random_grid(30, 40, 40 + seed * 3, seed);
+
+            for threshold in [0, 3, 4, 6, 9] {
+                let mut grid = Grid::from(data.as_str());
+                let mut rescanned_grid = grid.clone();
+
+                assert_eq!(
+                    ForkLiftsHelper::new_with_threshold(&mut grid, threshold).run_with_history(),
+                    rescan_history(&mut rescanned_grid, threshold)
+                );
+                assert_eq!(grid, rescanned_grid);
+            }
+
+            let mut grid = Grid::from(data.as_str()).with_mode(GridMode::Toroidal);
+            let mut sparse_grid = SparseGrid::from(&grid);
+
+            assert_eq!(
+                ForkLiftsHelper::new(&mut grid).run_with_history(),
+                rescan_history(&mut sparse_grid, 4)
+            );
+        }
+
+        let data = "@#@@\n@##@\n@@@@";
+
+        assert_eq!(
+            ForkLiftsHelper::new(&mut Grid::from(data)).run_with_history(),
+            rescan_history(&mut Grid::from(data), 4)
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_and_resume() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let path = TempPath::new("day_4_checkpoint");
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+
+        forklifts_helper.run_rounds(2);
+
+        assert_eq!(
+            forklifts_helper.progress(),
+            PeelProgress {
+                rounds: 2,
+                removed: 25,
+            }
+        );
+
+        forklifts_helper.save_checkpoint(&path.0).unwrap();
+
+        let (mut resumed_grid, checkpoint) = ForkLiftsHelper::load_checkpoint(&path.0).unwrap();
+
+        assert_eq!(resumed_grid, grid);
+        assert_eq!(checkpoint.progress.rounds, 2);
+
+        let mut forklifts_helper = ForkLiftsHelper::from_checkpoint(&mut resumed_grid, checkpoint);
+        let outcome = forklifts_helper.run_to_completion();
+
+        assert_eq!(outcome.removed, 18);
+        assert_eq!(
+            forklifts_helper.progress(),
+            PeelProgress {
+                rounds: 9,
+                removed: 43,
+            }
+        );
+
+        // Same totals when counted by the queue-based peel
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+        forklifts_helper.run_queue_based();
+
+        assert_eq!(forklifts_helper.progress().removed, 43);
+
+        // The peel settings and the grid mode are restored along with the progress
+        let mut grid = Grid::from(data).with_mode(GridMode::Toroidal);
+        let mut forklifts_helper = ForkLiftsHelper::new_with_threshold(&mut grid, 3)
+            .with_adjacency(Adjacency::Four)
+            .with_boundary_rule(BoundaryRule::OutsideIsOccupied)
+            .with_wall_rule(WallRule::WallIsEmpty);
+        let history = forklifts_helper.run_with_history();
+
+        let mut grid = Grid::from(data).with_mode(GridMode::Toroidal);
+        let mut forklifts_helper = ForkLiftsHelper::new_with_threshold(&mut grid, 3)
+            .with_adjacency(Adjacency::Four)
+            .with_boundary_rule(BoundaryRule::OutsideIsOccupied)
+            .with_wall_rule(WallRule::WallIsEmpty);
+        forklifts_helper.run_rounds(1);
+        forklifts_helper.save_checkpoint(&path.0).unwrap();
+
+        let (mut resumed_grid, checkpoint) = ForkLiftsHelper::load_checkpoint(&path.0).unwrap();
+
+        assert_eq!(resumed_grid.mode(), GridMode::Toroidal);
+        assert_eq!(
+            checkpoint,
+            PeelCheckpoint {
+                progress: PeelProgress {
+                    rounds: 1,
+                    removed: history[0].len(),
+                },
+                threshold: 3,
+                adjacency: Adjacency::Four,
+                boundary_rule: BoundaryRule::OutsideIsOccupied,
+                wall_rule: WallRule::WallIsEmpty,
+            }
+        );
+        assert_eq!(
+            ForkLiftsHelper::from_checkpoint(&mut resumed_grid, checkpoint).run_with_history(),
+            history[1..]
+        );
+    }
+
+    #[test]
+    fn test_invalid_checkpoint() {
+        let path = TempPath::new("day_4_invalid_checkpoint");
+        let header =
+            "rounds=1 removed=1 mode=bounded adjacency=8 threshold=4 boundary=empty walls=occupied";
+
+        let load_error = |checkpoint: &str| {
+            std::fs::write(&path.0, checkpoint).unwrap();
+            ForkLiftsHelper::load_checkpoint(&path.0)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert_eq!(
+            load_error("@@.\n.@@"),
+            "Unsupported checkpoint format '@@.'"
+        );
+        // Without the peel settings, it can't be resumed the same way
+        assert_eq!(
+            load_error("day_4 peel checkpoint v1\nrounds=1 removed=1\n@"),
+            "Unsupported checkpoint format 'day_4 peel checkpoint v1'"
+        );
+        assert_eq!(
+            load_error("day_4 peel checkpoint v2\nrounds=1 removed=1\n@"),
+            "Invalid checkpoint header 'rounds=1 removed=1'"
+        );
+        assert_eq!(
+            load_error(&format!(
+                "day_4 peel checkpoint v2\n{}\n@",
+                header.replace("adjacency=8", "adjacency=6")
+            )),
+            format!(
+                "Invalid checkpoint header '{}'",
+                header.replace("adjacency=8", "adjacency=6")
+            )
+        );
+        assert_eq!(
+            load_error(&format!("day_4 peel checkpoint v2\n{} extra=1\n@", header)),
+            format!("Invalid checkpoint header '{} extra=1'", header)
+        );
+        assert_eq!(
+            load_error(&format!("day_4 peel checkpoint v2\n{}\n@@\n@", header)),
+            "Invalid checkpoint grid: Inconsistent row width (row 1 has 1, expected 2)"
+        );
+
+        std::fs::remove_file(&path.0).unwrap();
+
+        assert!(matches!(
+            ForkLiftsHelper::load_checkpoint(&path.0),
+            Err(CheckpointError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_accessible_per_row_and_column() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let grid = Grid::from(data);
+        let scan = AccessibilityScan::new(&grid);
+
+        let (per_row, per_column) = scan.accessible_per_row_and_column();
+
+        assert_eq!(per_row, scan.accessible_per_row());
+        assert_eq!(per_column, scan.accessible_per_column());
+        assert_eq!(per_row, Vec::from([5, 1, 1, 0, 2, 0, 0, 1, 0, 3]));
+        assert_eq!(per_column, Vec::from([4, 0, 2, 1, 0, 1, 2, 0, 2, 1]));
+        assert_eq!(per_row.iter().sum::<usize>(), 13);
+        assert_eq!(per_column.iter().sum::<usize>(), 13);
+
+        let grid = Grid::from("@@@\n@@@");
+
+        assert_eq!(
+            AccessibilityScan::new(&grid)
+                .with_boundary_rule(BoundaryRule::OutsideIsOccupied)
+                .accessible_per_row(),
+            Vec::from([0, 0])
+        );
+        assert_eq!(
+            AccessibilityScan::new(&grid).accessible_per_column(),
+            Vec::from([2, 0, 2])
+        );
+        assert!(
+            AccessibilityScan::new(&Grid::from(""))
+                .accessible_per_row()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_round_stats() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let round_stats: Vec<RoundStats> = ForkLiftsHelper::new(&mut grid).rounds().collect();
+
+        let history = ForkLiftsHelper::new(&mut Grid::from(data)).run_with_history();
+
+        assert_eq!(
+            round_stats.iter().map(|stats| stats.removed).sum::<usize>(),
+            43
+        );
+        assert_eq!(
+            round_stats
+                .iter()
+                .map(|stats| stats.removed)
+                .collect::<Vec<usize>>(),
+            history
+                .iter()
+                .map(|round| round.len())
+                .collect::<Vec<usize>>()
+        );
+        assert_eq!(
+            round_stats.first(),
+            Some(&RoundStats {
+                round: 1,
+                removed: 13,
+                remaining: 58,
+            })
+        );
+        assert_eq!(
+            round_stats.last(),
+            Some(&RoundStats {
+                round: 9,
+                removed: 1,
+                remaining: 28,
+            })
+        );
+        assert_eq!(grid.count_paper_rolls(), 28);
+
+        // Only the first round is removed
+        let mut grid = Grid::from(data);
+        let first_round: Vec<RoundStats> =
+            ForkLiftsHelper::new(&mut grid).rounds().take(1).collect();
+
+        assert_eq!(first_round, round_stats[..1]);
+        assert_eq!(grid.count_paper_rolls(), 58);
+
+        // Resuming continues from the second round
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+        let next_round = forklifts_helper.rounds().next().unwrap();
+
+        assert_eq!(next_round.removed, 12);
+        assert_eq!(next_round.remaining, 46);
+
+        assert_eq!(
+            ForkLiftsHelper::new(&mut Grid::from("...\n.#."))
+                .rounds()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_boundary_rule() {
+        let grid = Grid::from("@@@");
+        let scan = AccessibilityScan::new(&grid);
+        let walled_scan = scan.with_boundary_rule(BoundaryRule::OutsideIsOccupied);
+
+        // 1 and 2 rolls around, plus 7 and 6 slots outside when walled
+        assert_eq!(scan.count_occupied_neighbors(0, 0), 1);
+        assert_eq!(scan.count_occupied_neighbors(0, 1), 2);
+        assert_eq!(walled_scan.count_occupied_neighbors(0, 0), 8);
+        assert_eq!(walled_scan.count_occupied_neighbors(0, 1), 8);
+        assert_eq!(scan.accessible_paper_rolls().count(), 3);
+        assert_eq!(walled_scan.accessible_paper_rolls().count(), 0);
+
+        let orthogonal_scan = walled_scan.with_adjacency(Adjacency::Four);
+        assert_eq!(orthogonal_scan.count_occupied_neighbors(0, 0), 4);
+        assert_eq!(orthogonal_scan.count_occupied_neighbors(0, 1), 4);
+
+        // Toroidal grids have nothing outside
+        let grid = Grid::from("@@@").with_mode(GridMode::Toroidal);
+        assert_eq!(
+            AccessibilityScan::new(&grid)
+                .with_boundary_rule(BoundaryRule::OutsideIsOccupied)
+                .count_occupied_neighbors(0, 1),
+            AccessibilityScan::new(&grid).count_occupied_neighbors(0, 1)
+        );
+
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper =
+            ForkLiftsHelper::new(&mut grid).with_boundary_rule(BoundaryRule::OutsideIsEmpty);
+
+        assert_eq!(
+            forklifts_helper.scan().find_accessible_paper_rolls().len(),
+            13
+        );
+        assert_eq!(
+            forklifts_helper.iterative_remove_accessible_paper_rolls(),
+            43
+        );
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper =
+            ForkLiftsHelper::new(&mut grid).with_boundary_rule(BoundaryRule::OutsideIsOccupied);
+
+        assert_eq!(
+            forklifts_helper
+                .scan()
+                .find_accessible_paper_rolls()
+                .collect::<Vec<Pos>>(),
+            Vec::from([Pos::new(2, 6)])
+        );
+
+        let history = forklifts_helper.run_with_history();
+
+        assert_eq!(history.iter().map(|round| round.len()).sum::<usize>(), 2);
+        assert_eq!(
+            history,
+            ForkLiftsHelper::new(&mut Grid::from(data))
+                .with_boundary_rule(BoundaryRule::OutsideIsOccupied)
+                .run_queue_based()
+        );
+    }
+
+    #[test]
+    fn test_wall_rule() {
+        let data = "@@#@@\n@@#@@\n@@#@@\n#####\n@@@@@";
+
+        let grid = Grid::from(data);
+        let scan = AccessibilityScan::new(&grid);
+        let open_scan = scan.with_wall_rule(WallRule::WallIsEmpty);
+
+        // 3 rolls around, plus 2 walls when they're occupied
+        assert_eq!(scan.count_occupied_neighbors(0, 1), 5);
+        assert_eq!(open_scan.count_occupied_neighbors(0, 1), 3);
+        assert_eq!(scan.count_occupied_neighbors(4, 2), 5);
+        assert_eq!(open_scan.count_occupied_neighbors(4, 2), 2);
+
+        // The neighbor count map only holds the rolls, walls are added when occupied
+        let neighbor_counts = grid.compute_neighbor_count_map();
+        assert_eq!(neighbor_counts[0][1], 3);
+        assert_eq!(
+            open_scan
+                .with_neighbor_count_map(&neighbor_counts)
+                .count_occupied_neighbors(0, 1),
+            3
+        );
+        assert_eq!(
+            scan.with_neighbor_count_map(&neighbor_counts)
+                .count_occupied_neighbors(0, 1),
+            5
+        );
+
+        let mut grid = Grid::from(data);
+        let history = ForkLiftsHelper::new(&mut grid).run_with_history();
+
+        assert_eq!(
+            history,
+            Vec::from([Vec::from([
+                Pos::new(0, 0),
+                Pos::new(0, 4),
+                Pos::new(4, 0),
+                Pos::new(4, 4)
+            ])])
+        );
+
+        let mut grid = Grid::from(data);
+        let peel_outcome = ForkLiftsHelper::new(&mut grid)
+            .with_wall_rule(WallRule::WallIsEmpty)
+            .run_to_completion();
+
+        assert_eq!(
+            peel_outcome,
+            PeelOutcome {
+                removed: 17,
+                rounds: 2,
+                remaining: 0,
+                removed_per_round: Vec::from([13, 4]),
+            }
+        );
+        assert_eq!(grid.to_string(), "..#..\n..#..\n..#..\n#####\n.....");
+        assert_eq!(
+            ForkLiftsHelper::new(&mut Grid::from(data))
+                .with_wall_rule(WallRule::WallIsEmpty)
+                .run_queue_based()
+                .concat()
+                .len(),
+            17
+        );
+    }
+
+    #[test]
+    fn test_double_buffered_peel() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let mut rescanned_grid = grid.clone();
+
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+        let history = forklifts_helper.run_double_buffered();
+
+        assert_eq!(forklifts_helper.progress().removed, 43);
+        assert_eq!(history, rescan_history(&mut rescanned_grid, 4));
+        assert_eq!(grid, rescanned_grid);
+
+        // Random grids with walls, under every mode, adjacency and rule
+        for seed in 0..20 {
+            let mut data = random_grid(30, 70, 40 + seed * 3, seed).into_bytes();
+
+            for index in (seed as usize..data.len()).step_by(37) {
+                if data[index] != b'\n' {
+                    data[index] = b'#';
+                }
+            }
+
+            let data = String::from_utf8(data).unwrap();
+
+            for mode in [GridMode::Bounded, GridMode::Toroidal] {
+                for adjacency in [Adjacency::Eight, Adjacency::Four] {
+                    for (boundary_rule, wall_rule) in [
+                        (BoundaryRule::OutsideIsEmpty, WallRule::WallIsOccupied),
+                        (BoundaryRule::OutsideIsOccupied, WallRule::WallIsEmpty),
+                    ] {
+                        for threshold in [0, 2, 4, 9] {
+                            let mut grid = Grid::from(data.as_str()).with_mode(mode);
+                            let mut incremental_grid = grid.clone();
+
+                            let history = ForkLiftsHelper::new_with_threshold(&mut grid, threshold)
+                                .with_adjacency(adjacency)
+                                .with_boundary_rule(boundary_rule)
+                                .with_wall_rule(wall_rule)
+                                .run_double_buffered();
+
+                            assert_eq!(
+                                history,
+                                ForkLiftsHelper::new_with_threshold(
+                                    &mut incremental_grid,
+                                    threshold
+                                )
+                                .with_adjacency(adjacency)
+                                .with_boundary_rule(boundary_rule)
+                                .with_wall_rule(wall_rule)
+                                .run_with_history()
+                            );
+                            assert_eq!(grid, incremental_grid);
+                        }
+                    }
+                }
+            }
+        }
+
+        assert!(
+            ForkLiftsHelper::new(&mut Grid::from(""))
+                .run_double_buffered()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_queue_based_peel() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let history = ForkLiftsHelper::new(&mut grid).run_queue_based();
+
+        assert_eq!(history.iter().map(|round| round.len()).sum::<usize>(), 43);
+        assert_eq!(
+            history,
+            ForkLiftsHelper::new(&mut Grid::from(data)).run_with_history()
+        );
+        assert_eq!(grid.count_paper_rolls(), 28);
+
+        for seed in 0..20 {
+            let data = random_grid(30, 70, 40 + seed * 3, seed);
+
+            for mode in [GridMode::Bounded, GridMode::Toroidal] {
+                for adjacency in [Adjacency::Eight, Adjacency::Four] {
+                    for threshold in [0, 2, 4, 9] {
+                        let mut grid = Grid::from(data.as_str()).with_mode(mode);
+                        let mut bit_grid = BitGrid::from(&grid);
+
+                        let history = ForkLiftsHelper::new_with_threshold(&mut grid, threshold)
+                            .with_adjacency(adjacency)
+                            .run_queue_based();
+
+                        assert_eq!(
+                            history,
+                            ForkLiftsHelper::new_with_threshold(&mut bit_grid, threshold)
+                                .with_adjacency(adjacency)
+                                .run_with_history()
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut grid = Grid::from("@#@@\n@##@\n@@@@");
+        let mut rescanned_grid = grid.clone();
+
+        assert_eq!(
+            ForkLiftsHelper::new(&mut grid).run_queue_based(),
+            rescan_history(&mut rescanned_grid, 4)
+        );
+        assert_eq!(grid, rescanned_grid);
+    }
+
+    #[test]
+    fn test_snapshot_and_partial_rounds() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let snapshot = grid.snapshot();
+
+        let first_history = ForkLiftsHelper::new(&mut grid).run_rounds(2);
+
+        assert_eq!(
+            first_history
+                .iter()
+                .map(|round| round.len())
+                .collect::<Vec<usize>>(),
+            Vec::from([13, 12])
+        );
+        assert_eq!(grid.count_paper_rolls(), 71 - 25);
+
+        grid.restore(&snapshot);
+
+        assert_eq!(ForkLiftsHelper::new(&mut grid).run_rounds(2), first_history);
+
+        // Resuming gives the remaining rounds of a full peel
+        let remaining_history = ForkLiftsHelper::new(&mut grid).run_rounds(usize::MAX);
+
+        assert_eq!(
+            [first_history, remaining_history].concat(),
+            ForkLiftsHelper::new(&mut Grid::from(data)).run_with_history()
+        );
+        assert!(ForkLiftsHelper::new(&mut grid).run_rounds(1).is_empty());
+
+        grid.restore(&snapshot);
+
+        assert!(ForkLiftsHelper::new(&mut grid).run_rounds(0).is_empty());
+        assert_eq!(grid.count_paper_rolls(), 71);
+
+        ForkLiftsHelper::new(&mut grid).run_to_completion();
+
+        grid.restore(&snapshot);
+
+        assert_eq!(grid.count_paper_rolls(), 71);
+        assert_eq!(grid.to_string(), Grid::from(data).to_string());
+    }
+
+    #[test]
+    fn test_diff_after_peel_round() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let grid_before = Grid::from(data);
+        let mut grid = Grid::from(data);
+
+        assert!(grid == grid_before);
+
+        let history = ForkLiftsHelper::new(&mut grid).run_rounds(1);
+        let grid_diff = grid_before.diff(&grid).unwrap();
+
+        let removed: Vec<(usize, usize)> = history[0].iter().map(|&pos| pos.into()).collect();
+
+        assert!(grid != grid_before);
+        assert_eq!(grid_diff.only_in_self, removed);
+        assert!(grid_diff.only_in_other.is_empty());
+        assert_eq!(grid.diff(&grid_before).unwrap().only_in_other, removed);
+    }
+
+    #[test]
+    fn test_peak_round() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let peel_outcome = ForkLiftsHelper::new(&mut Grid::from(data)).run_to_completion();
+
+        assert_eq!(peel_outcome.peak_round(), Some((0, 13)));
+        // Several rounds remove a single roll, the first of them wins
+        assert_eq!(peel_outcome.min_round(), Some((5, 1)));
+        assert!((peel_outcome.mean_removed_per_round().unwrap() - 43.0 / 9.0).abs() < 1e-9);
+
+        let peel_outcome = PeelOutcome::from_history(
+            &[
+                Vec::from([Pos::new(0, 0)]),
+                Vec::from([Pos::new(0, 1), Pos::new(0, 2)]),
+                Vec::from([Pos::new(1, 0), Pos::new(1, 1)]),
+            ],
+            3,
+        );
+
+        assert_eq!(peel_outcome.removed, 5);
+        assert_eq!(peel_outcome.peak_round(), Some((1, 2)));
+        assert_eq!(peel_outcome.min_round(), Some((0, 1)));
+
+        let peel_outcome = ForkLiftsHelper::new(&mut Grid::from("")).run_to_completion();
+
+        assert_eq!(peel_outcome.peak_round(), None);
+        assert_eq!(peel_outcome.min_round(), None);
+        assert_eq!(peel_outcome.mean_removed_per_round(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_peel_results_json() {
+        let mut grid = Grid::from("@@@\n@@@\n@@@");
+
+        let history = ForkLiftsHelper::new(&mut grid).run_with_history();
+        let peel_outcome = PeelOutcome {
+            removed: 9,
+            rounds: 3,
+            remaining: 0,
+            removed_per_round: Vec::from([4, 4, 1]),
+        };
+
+        let json = serde_json::to_string(&(&peel_outcome, &history)).unwrap();
+
+        assert_eq!(
+            json,
+            r#"[{"removed":9,"rounds":3,"remaining":0,"removed_per_round":[4,4,1]},[[[0,0],[0,2],[2,0],[2,2]],[[0,1],[1,0],[1,2],[2,1]],[[1,1]]]]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<(PeelOutcome, Vec<Vec<Pos>>)>(&json).unwrap(),
+            (peel_outcome, history)
+        );
+    }
+
+    #[test]
+    fn test_occupancy_after_peel() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let initial_paper_rolls = grid.count(GridType::PaperRoll);
+
+        assert_eq!(initial_paper_rolls, 71);
+        assert_eq!(grid.count(GridType::Empty), 29);
+        assert_eq!(grid.bounding_box_of_rolls(), Some((0, 0, 9, 9)));
+        assert!(!grid.is_cleared());
+        assert_eq!(grid.count_roll_components(Adjacency::Eight), 1);
+        assert_eq!(grid.count_roll_components(Adjacency::Four), 3);
+
+        let removed_paper_rolls =
+            ForkLiftsHelper::new(&mut grid).iterative_remove_accessible_paper_rolls();
+
+        assert_eq!(removed_paper_rolls, 43);
+        assert_eq!(grid.count(GridType::PaperRoll), initial_paper_rolls - 43);
+        assert_eq!(grid.bounding_box_of_rolls(), Some((3, 3, 9, 8)));
+        assert!(!grid.is_cleared());
+        assert_eq!(grid.count_roll_components(Adjacency::Eight), 1);
+        assert_eq!(grid.roll_components(Adjacency::Four)[0].len(), 28);
+
+        let mut grid = Grid::from("@.@\n...\n.@.");
+
+        ForkLiftsHelper::new(&mut grid).iterative_remove_accessible_paper_rolls();
+
+        assert!(grid.is_cleared());
+        assert_eq!(grid.bounding_box_of_rolls(), None);
+    }
+
+    #[test]
+    fn test_orthogonal_adjacency() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid).with_adjacency(Adjacency::Eight);
+
+        assert_eq!(
+            forklifts_helper.scan().find_accessible_paper_rolls().len(),
+            13
+        );
+        assert_eq!(
+            forklifts_helper.iterative_remove_accessible_paper_rolls(),
+            43
+        );
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid).with_adjacency(Adjacency::Four);
+
+        // Only the rolls surrounded on all four sides stay, and they all go in the second round
+        assert_eq!(
+            forklifts_helper.scan().find_accessible_paper_rolls().len(),
+            58
+        );
+        assert_eq!(
+            forklifts_helper.run_to_completion(),
+            PeelOutcome {
+                removed: 71,
+                rounds: 2,
+                remaining: 0,
+                removed_per_round: Vec::from([58, 13])
+            }
+        );
+
+        let grid = Grid::from(data);
+        let accessibility_scan =
+            AccessibilityScan::new_with_threshold(&grid, 3).with_adjacency(Adjacency::Four);
+
+        assert_eq!(accessibility_scan.find_accessible_paper_rolls().len(), 37);
+        assert_eq!(Adjacency::Four.offsets().len(), 4);
+    }
+
+    #[test]
+    fn test_accessibility_threshold() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper = ForkLiftsHelper::new_with_threshold(&mut grid, 4);
+
+        assert_eq!(
+            forklifts_helper.scan().find_accessible_paper_rolls().len(),
+            13
+        );
+        assert_eq!(
+            forklifts_helper.iterative_remove_accessible_paper_rolls(),
+            43
+        );
+
+        let mut grid = Grid::from(data);
+        let peel_outcome = ForkLiftsHelper::new_with_threshold(&mut grid, 9).run_to_completion();
+
+        assert_eq!(
+            peel_outcome,
+            PeelOutcome {
+                removed: 71,
+                rounds: 1,
+                remaining: 0,
+                removed_per_round: Vec::from([71])
+            }
+        );
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper = ForkLiftsHelper::new_with_threshold(&mut grid, 0);
+
+        assert_eq!(
+            forklifts_helper.scan().find_accessible_paper_rolls().len(),
+            0
+        );
+        assert_eq!(
+            forklifts_helper.iterative_remove_accessible_paper_rolls(),
+            0
+        );
+        assert_eq!(grid.count_paper_rolls(), 71);
+    }
+
+    #[test]
+    fn test_max_neighbors_threshold() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data);
+
+        assert_eq!(
+            ForkLiftsHelper::with_threshold(&mut grid, 3)
+                .scan()
+                .find_accessible_paper_rolls()
+                .len(),
+            13
+        );
+
+        // Only one roll is fully surrounded
+        assert_eq!(
+            ForkLiftsHelper::with_threshold(&mut grid, 7)
+                .scan()
+                .find_accessible_paper_rolls()
+                .len(),
+            70
+        );
+        assert_eq!(
+            ForkLiftsHelper::with_threshold(&mut grid, 0)
+                .scan()
+                .find_accessible_paper_rolls()
+                .len(),
+            grid.count_isolated_paper_rolls()
+        );
+
+        let mut grid = Grid::from("@.@.@\n...@@\n@.@.@");
+        let isolated_paper_rolls = grid.count_isolated_paper_rolls();
+
+        assert_eq!(isolated_paper_rolls, 2);
+        assert_eq!(
+            ForkLiftsHelper::with_threshold(&mut grid, 0)
+                .scan()
+                .accessible_paper_rolls()
+                .collect::<Vec<Pos>>(),
+            Vec::from([Pos::new(0, 0), Pos::new(2, 0)])
+        );
+        assert_eq!(
+            ForkLiftsHelper::with_threshold(&mut grid, 0).run_to_completion(),
+            PeelOutcome {
+                removed: 2,
+                rounds: 1,
+                remaining: 6,
+                removed_per_round: Vec::from([2])
+            }
+        );
+    }
+
+    #[test]
+    fn test_removal_history() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let initial_grid = Grid::from(data);
+        let mut grid = Grid::from(data);
+
+        let history = ForkLiftsHelper::new(&mut grid).run_with_history();
+
+        assert_eq!(history.len(), 9);
+        assert_eq!(history[0].len(), 13);
+
+        let removed_paper_rolls: Vec<Pos> = history.iter().flatten().copied().collect();
+        let unique_paper_rolls: HashSet<&Pos> = removed_paper_rolls.iter().collect();
+
+        assert_eq!(removed_paper_rolls.len(), 43);
+        assert_eq!(unique_paper_rolls.len(), 43);
+        assert!(
+            removed_paper_rolls
+                .iter()
+                .all(|pos| initial_grid.is_paper_roll(pos.row, pos.col))
+        );
+        assert!(history.iter().all(|round| round.is_sorted()));
+    }
+
+    #[test]
+    fn test_display_grid_after_removal() {
+        let data = r#"
+            @@@@
+            @@@@
+            @@@@
+            @@@@"#;
+
+        let mut grid = Grid::from(data);
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+
+        // Only the corners have less than 4 neighbors, then every edge roll keeps 4
+        assert_eq!(
+            forklifts_helper.iterative_remove_accessible_paper_rolls(),
+            4
+        );
+
+        assert_eq!(grid.to_string(), ".@@.\n@@@@\n@@@@\n.@@.");
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_scan() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let random_data = random_grid(200, 200, 65, 2025);
+
+        for data in [data, random_data.as_str()] {
+            let grid = Grid::from(data);
+            let accessibility_scan = AccessibilityScan::new(&grid);
+
+            // The grids are below the size threshold, so this is the sequential scan
+            let accessible_paper_rolls: Vec<Pos> =
+                accessibility_scan.find_accessible_paper_rolls().collect();
+
+            assert!(!accessible_paper_rolls.is_empty());
+            assert_eq!(
+                accessibility_scan.find_accessible_paper_rolls_parallel(),
+                accessible_paper_rolls
+            );
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_double_buffered_round() {
+        let data = random_grid(200, 200, 65, 2025);
+        let mut grid = Grid::from(data.as_str());
+        let forklifts_helper = ForkLiftsHelper::new(&mut grid);
+
+        let mut current = forklifts_helper.buffer_cells();
+
+        // Below the size threshold, so this is the sequential round
+        for _ in 0..5 {
+            let mut next = current.clone();
+            let mut parallel_next = current.clone();
+
+            let removed = forklifts_helper.double_buffered_round(&current, &mut next);
+
+            assert!(!removed.is_empty());
+            assert_eq!(
+                forklifts_helper.double_buffered_round_parallel(&current, &mut parallel_next),
+                removed
+            );
+            assert_eq!(parallel_next, next);
+
+            current = next;
+        }
+    }
+
+    #[test]
+    fn test_render_round() {
+        let mut grid = Grid::from("@@@\n@@@\n@@@");
+        let grid_before = Grid::from("@@@\n@@@\n@@@");
+
+        let history = ForkLiftsHelper::new(&mut grid).run_with_history();
+
+        assert_eq!(render_round(&grid_before, &history[0]), "x@x\n@@@\nx@x");
+        assert_eq!(render_round(&grid_before, &[]), "@@@\n@@@\n@@@");
+    }
+
+    #[test]
+    fn test_wall_corridor() {
+        let grid = Grid::from("@@@@\n....\n@@@@");
+        let accessibility_scan = AccessibilityScan::new(&grid);
+
+        assert_eq!(accessibility_scan.find_accessible_paper_rolls().len(), 8);
+
+        let mut grid = Grid::from("@@@@\n####\n@@@@").with_removed_markers(true);
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+
+        // Walls take a neighbor slot, so only the corners stay below 4
+        assert!(forklifts_helper.scan().is_item_accessible(0, 0));
+        assert!(!forklifts_helper.scan().is_item_accessible(0, 1));
+        assert!(!forklifts_helper.scan().is_item_accessible(1, 0));
+        assert_eq!(
+            forklifts_helper
+                .scan()
+                .find_accessible_paper_rolls()
+                .collect::<Vec<Pos>>(),
+            Vec::from([
+                Pos::new(0, 0),
+                Pos::new(0, 3),
+                Pos::new(2, 0),
+                Pos::new(2, 3)
+            ])
+        );
+
+        assert_eq!(
+            forklifts_helper.run_to_completion(),
+            PeelOutcome {
+                removed: 4,
+                rounds: 1,
+                remaining: 4,
+                removed_per_round: Vec::from([4])
+            }
+        );
+        assert_eq!(grid.to_string(), "x@@x\n####\nx@@x");
+    }
+
+    #[test]
+    fn test_full_torus() {
+        let data = "@@@\n@@@\n@@@";
+
+        let grid = Grid::from(data).with_mode(GridMode::Toroidal);
+        let accessibility_scan = AccessibilityScan::new(&grid);
+
+        assert!(
+            (0..3)
+                .flat_map(|row| (0..3).map(move |col| (row, col)))
+                .all(
+                    |(row, col)| accessibility_scan.grid.occupied_neighbor_count(
+                        row,
+                        col,
+                        Adjacency::Eight
+                    ) == 8
+                )
+        );
+        assert_eq!(accessibility_scan.accessible_paper_rolls().count(), 0);
+
+        let grid = Grid::from(data);
+        let accessibility_scan = AccessibilityScan::new(&grid);
+
+        assert_eq!(
+            accessibility_scan
+                .accessible_paper_rolls()
+                .collect::<Vec<Pos>>(),
+            Vec::from([
+                Pos::new(0, 0),
+                Pos::new(0, 2),
+                Pos::new(2, 0),
+                Pos::new(2, 2)
+            ])
+        );
+
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data).with_mode(GridMode::Bounded);
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+
+        assert_eq!(
+            forklifts_helper.scan().find_accessible_paper_rolls().len(),
+            13
+        );
+        assert_eq!(
+            forklifts_helper.iterative_remove_accessible_paper_rolls(),
+            43
+        );
+    }
+
+    #[test]
+    fn test_toroidal_accessibility() {
+        let data = r#"
+            @@.@
+            @@.@
+            ....
+            @@.@"#;
+
+        let grid = Grid::from(data);
+        let accessibility_scan = AccessibilityScan::new(&grid);
+
+        assert!(accessibility_scan.is_item_accessible(0, 0));
+        assert_eq!(accessibility_scan.find_accessible_paper_rolls().count(), 9);
+
+        let grid = Grid::from(data).with_mode(GridMode::Toroidal);
+        let accessibility_scan = AccessibilityScan::new(&grid);
+
+        // The corner touches the rolls on the opposite edges
+        assert!(!accessibility_scan.is_item_accessible(0, 0));
+        let accessible_paper_rolls: Vec<Pos> =
+            accessibility_scan.find_accessible_paper_rolls().collect();
+
+        assert_eq!(
+            accessible_paper_rolls,
+            Vec::from([
+                Pos::new(1, 1),
+                Pos::new(1, 3),
+                Pos::new(3, 1),
+                Pos::new(3, 3)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_stuck_rolls() {
+        let grid = Grid::from(
+            r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#,
+        );
+        let grid_before = grid.to_string();
+
+        let stuck_rolls = grid.stuck_rolls(4, Adjacency::Eight);
+
+        assert_eq!(stuck_rolls.len(), grid.count_paper_rolls() - 43);
+        assert_eq!(stuck_rolls.first(), Some(&(3, 4)));
+        assert_eq!(stuck_rolls.last(), Some(&(9, 6)));
+        assert!(stuck_rolls.contains(&(6, 3)));
+        assert!(!stuck_rolls.contains(&(6, 4)));
+        assert!(stuck_rolls.is_sorted());
+        assert!(!grid.is_fully_clearable());
+
+        // The peel ran on a copy
+        assert_eq!(grid.to_string(), grid_before);
+        assert_eq!(grid.count_paper_rolls(), 71);
+
+        assert!(grid.stuck_rolls(9, Adjacency::Eight).is_empty());
+        assert_eq!(grid.stuck_rolls(0, Adjacency::Eight).len(), 71);
+
+        let full_block = Grid::from("@@@\n@@@\n@@@");
+
+        assert!(full_block.is_fully_clearable());
+        assert!(full_block.stuck_rolls(4, Adjacency::Eight).is_empty());
+        assert_eq!(full_block.count_paper_rolls(), 9);
+
+        // Walls are never removed, so they don't count as stuck rolls
+        assert!(Grid::from("@#@\n###").is_fully_clearable());
+        assert!(!Grid::from("###\n#@#\n###").is_fully_clearable());
+    }
+}
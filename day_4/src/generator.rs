@@ -0,0 +1,130 @@
+//! Seeded warehouse grids generator for tests and benchmarks.
+//!
+//! Uses a xorshift64 generator so the same seed produces the same grid on
+//! every platform.
+
+use crate::Grid;
+
+struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        // A zero state would only ever produce zeros
+        let state = match seed {
+            0 => 0x9E37_79B9_7F4A_7C15,
+            seed => seed,
+        };
+
+        Self { state }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        self.state
+    }
+}
+
+/// Whether each cell is a paper roll, in row-major order
+fn random_cells(n_cells: usize, fill_percent: u64, seed: u64) -> impl Iterator<Item = bool> {
+    assert!(fill_percent <= 100, "Fill percent must be between 0-100");
+
+    let mut rng = XorShift::new(seed);
+
+    (0..n_cells).map(move |_| rng.next() % 100 < fill_percent)
+}
+
+/// Generate a grid in the puzzle format where `fill_percent` of the cells are paper rolls on average
+pub fn random_grid(n_rows: usize, n_columns: usize, fill_percent: u64, seed: u64) -> String {
+    let mut cells = random_cells(n_rows * n_columns, fill_percent, seed);
+
+    (0..n_rows)
+        .map(|_| {
+            cells
+                .by_ref()
+                .take(n_columns)
+                .map(|is_paper_roll| match is_paper_roll {
+                    true => '@',
+                    false => '.',
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Parsed version of `random_grid`, with the density as a fraction of paper rolls between 0-1
+pub fn random_warehouse(n_rows: usize, n_columns: usize, density: f64, seed: u64) -> Grid {
+    assert!(
+        (0.0..=1.0).contains(&density),
+        "Density must be between 0-1"
+    );
+
+    let fill_percent = (density * 100.0).round() as u64;
+
+    let rolls = random_cells(n_rows * n_columns, fill_percent, seed)
+        .enumerate()
+        .filter(|&(_, is_paper_roll)| is_paper_roll)
+        .map(|(index, _)| (index / n_columns, index % n_columns));
+
+    Grid::from_coords(n_rows, n_columns, rolls, true).expect("Generated rolls are within the grid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GridStore;
+
+    #[test]
+    fn deterministic_for_fixed_seed() {
+        assert_eq!(random_grid(20, 30, 60, 42), random_grid(20, 30, 60, 42));
+        assert_ne!(random_grid(20, 30, 60, 42), random_grid(20, 30, 60, 43));
+    }
+
+    #[test]
+    fn generated_grid_shape() {
+        let grid = random_grid(20, 30, 60, 1);
+
+        assert_eq!(grid.lines().count(), 20);
+        assert!(grid.lines().all(|row| row.len() == 30));
+
+        let paper_rolls = grid.chars().filter(|&char| char == '@').count();
+        assert!((300..=420).contains(&paper_rolls));
+
+        assert!(!random_grid(5, 5, 0, 1).contains('@'));
+        assert!(!random_grid(5, 5, 100, 1).contains('.'));
+    }
+
+    #[test]
+    fn deterministic_warehouse_for_fixed_seed() {
+        let grid = random_warehouse(100, 80, 0.6, 42);
+        let same_grid = random_warehouse(100, 80, 0.6, 42);
+
+        assert_eq!(grid.n_rows(), 100);
+        assert_eq!(grid.n_columns(), 80);
+        assert_eq!(grid.count_paper_rolls(), same_grid.count_paper_rolls());
+        assert_eq!(
+            grid.paper_rolls().take(10).collect::<Vec<(usize, usize)>>(),
+            same_grid
+                .paper_rolls()
+                .take(10)
+                .collect::<Vec<(usize, usize)>>()
+        );
+        assert!(grid == same_grid);
+        assert!(grid != random_warehouse(100, 80, 0.6, 43));
+        assert!(grid == Grid::from(random_grid(100, 80, 60, 42).as_str()));
+
+        assert_eq!(random_warehouse(10, 10, 0.0, 1).count_paper_rolls(), 0);
+        assert_eq!(random_warehouse(10, 10, 1.0, 1).count_paper_rolls(), 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Density must be between 0-1")]
+    fn invalid_density_panic() {
+        let _ = random_warehouse(10, 10, 1.5, 1);
+    }
+}
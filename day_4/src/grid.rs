@@ -0,0 +1,2113 @@
+use std::{
+    error::Error,
+    fmt,
+    hash::{Hash, Hasher},
+    io::{self, BufRead},
+    ops::Range,
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Adjacency, GridStore};
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum GridType {
+    Empty,
+    PaperRoll,
+    /// Fixed shelving, occupies a neighbor slot but can never be removed
+    Wall,
+    /// Paper roll taken by the forklifts, only kept when the grid uses removed markers and
+    /// rendered as 'x', which isn't parsed back
+    Removed,
+}
+
+impl GridType {
+    /// Whether the cell takes a neighbor slot when checking accessibility
+    fn is_occupied(&self) -> bool {
+        matches!(self, Self::PaperRoll | Self::Wall)
+    }
+
+    pub(crate) fn from_char(value: char) -> Option<Self> {
+        match value {
+            '.' => Some(Self::Empty),
+            '@' => Some(Self::PaperRoll),
+            '#' => Some(Self::Wall),
+            _ => None,
+        }
+    }
+}
+
+impl From<&char> for GridType {
+    fn from(value: &char) -> Self {
+        Self::from_char(*value).expect("Invalid char received")
+    }
+}
+
+impl From<&GridType> for char {
+    fn from(value: &GridType) -> Self {
+        match value {
+            GridType::Empty => '.',
+            GridType::PaperRoll => '@',
+            GridType::Wall => '#',
+            GridType::Removed => 'x',
+        }
+    }
+}
+
+/// Stored as its single char, so checkpoints stay small
+#[cfg(feature = "serde")]
+impl Serialize for GridType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(char::from(self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for GridType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Removed markers aren't parsed from the puzzle input, but they are part of a checkpoint
+        match char::deserialize(deserializer)? {
+            'x' => Ok(Self::Removed),
+            char => Self::from_char(char).ok_or_else(|| {
+                serde::de::Error::custom(format!("Invalid grid character '{}'", char))
+            }),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum GridParseError {
+    InconsistentRowWidth {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+    InvalidCharacter {
+        char: char,
+        row: usize,
+        col: usize,
+    },
+    Io {
+        row: usize,
+        kind: io::ErrorKind,
+    },
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InconsistentRowWidth {
+                row,
+                width,
+                expected,
+            } => write!(
+                f,
+                "Inconsistent row width (row {} has {}, expected {})",
+                row, width, expected
+            ),
+            Self::InvalidCharacter { char, row, col } => write!(
+                f,
+                "Invalid character {:?} at row {}, column {}",
+                char, row, col
+            ),
+            Self::Io { row, kind } => write!(f, "Failed to read row {}: {}", row, kind),
+        }
+    }
+}
+
+impl Error for GridParseError {}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct OutOfBoundsError {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for OutOfBoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Index {} out of bounds for length {}",
+            self.index, self.len
+        )
+    }
+}
+
+impl Error for OutOfBoundsError {}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct DimensionMismatch {
+    pub expected: (usize, usize),
+    pub found: (usize, usize),
+}
+
+impl fmt::Display for DimensionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected a {}x{} grid but found a {}x{} one",
+            self.expected.0, self.expected.1, self.found.0, self.found.1
+        )
+    }
+}
+
+impl Error for DimensionMismatch {}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum GridBuildError {
+    OutOfBounds { row: usize, col: usize },
+    DuplicateRoll { row: usize, col: usize },
+}
+
+impl fmt::Display for GridBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { row, col } => {
+                write!(f, "Paper roll at ({}, {}) is out of the grid", row, col)
+            }
+            Self::DuplicateRoll { row, col } => {
+                write!(
+                    f,
+                    "Paper roll at ({}, {}) is given more than once",
+                    row, col
+                )
+            }
+        }
+    }
+}
+
+impl Error for GridBuildError {}
+
+/// Paper rolls present in only one of two grids with the same dimensions
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct GridDiff {
+    /// Sorted in row-major order
+    pub only_in_self: Vec<(usize, usize)>,
+    /// Sorted in row-major order
+    pub only_in_other: Vec<(usize, usize)>,
+}
+
+impl GridDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty() && self.only_in_other.is_empty()
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum GridMode {
+    Bounded,
+    /// Opposite edges are connected like on a torus, wrapping around out-of-range positions
+    Toroidal,
+}
+
+impl GridMode {
+    /// Position in a grid of the given dimensions for the signed coordinates: `None` when they
+    /// fall out of a bounded grid, wrapped around the edges of a toroidal one
+    pub(crate) fn wrap_position(
+        self,
+        (n_rows, n_columns): (usize, usize),
+        row: isize,
+        col: isize,
+    ) -> Option<(usize, usize)> {
+        match self {
+            Self::Bounded => {
+                (row >= 0 && row < n_rows as isize && col >= 0 && col < n_columns as isize)
+                    .then_some((row as usize, col as usize))
+            }
+            // Nothing to wrap around in an empty grid
+            Self::Toroidal => (n_rows > 0 && n_columns > 0).then(|| {
+                (
+                    row.rem_euclid(n_rows as isize) as usize,
+                    col.rem_euclid(n_columns as isize) as usize,
+                )
+            }),
+        }
+    }
+}
+
+/// Rectangular grid of cells, the warehouse ones by default. Other cell types get the parsing,
+/// indexing, rendering and neighbors utilities, while the peel needs warehouse cells
+#[derive(Clone)]
+pub struct Grid<T = GridType> {
+    n_rows: usize,
+    n_columns: usize,
+    /// Cells in row-major order, see `index`
+    data: Vec<T>,
+    mode: GridMode,
+    /// Cell left in place of a removed one instead of the empty cell, see `with_removed_markers`
+    removed_cell: Option<T>,
+}
+
+pub type WarehouseGrid = Grid<GridType>;
+
+/// Grid as stored in a checkpoint, its dimensions are taken from the rows once validated
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct GridRecord {
+    data: Vec<Vec<GridType>>,
+    mode: GridMode,
+    removed_markers: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<GridRecord> for Grid {
+    type Error = GridParseError;
+
+    fn try_from(record: GridRecord) -> Result<Self, Self::Error> {
+        let n_columns = record.data.first().map_or(0, |row_data| row_data.len());
+
+        if let Some((row, row_data)) = record
+            .data
+            .iter()
+            .enumerate()
+            .find(|(_, row_data)| row_data.len() != n_columns)
+        {
+            return Err(GridParseError::InconsistentRowWidth {
+                row,
+                width: row_data.len(),
+                expected: n_columns,
+            });
+        }
+
+        Ok(Grid::new(
+            record.data.len(),
+            n_columns,
+            record.data.into_iter().flatten().collect(),
+        )
+        .with_mode(record.mode)
+        .with_removed_markers(record.removed_markers))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Grid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Grid::try_from(GridRecord::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Written with the same shape as `GridRecord`, one array per row
+#[cfg(feature = "serde")]
+impl Serialize for Grid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("Grid", 3)?;
+        state.serialize_field("data", &self.rows().collect::<Vec<&[GridType]>>())?;
+        state.serialize_field("mode", &self.mode)?;
+        state.serialize_field("removed_markers", &self.removed_cell.is_some())?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Grid {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Grid is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<Grid, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Copy of the grid cells at some point, to rewind the grid without parsing it again
+#[derive(Clone, Debug)]
+pub struct GridSnapshot {
+    n_rows: usize,
+    n_columns: usize,
+    data: Vec<GridType>,
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    fn new(n_rows: usize, n_columns: usize, data: Vec<T>) -> Self {
+        debug_assert_eq!(data.len(), n_rows * n_columns);
+
+        Self {
+            n_rows,
+            n_columns,
+            data,
+            mode: GridMode::Bounded,
+            removed_cell: None,
+        }
+    }
+
+    /// Position of a cell within the row-major data
+    fn index(&self, row: usize, col: usize) -> usize {
+        debug_assert!(
+            row < self.n_rows && col < self.n_columns,
+            "Position ({}, {}) outside of the grid",
+            row,
+            col
+        );
+
+        row * self.n_columns + col
+    }
+
+    /// Number of rows and columns, also available through `GridStore` for warehouse grids
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.n_rows, self.n_columns)
+    }
+
+    /// Cells of every row, from top to bottom
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        // Chunks can't be empty, but there is no data to split with zero columns anyway
+        self.data.chunks_exact(self.n_columns.max(1))
+    }
+
+    pub fn with_mode(mut self, mode: GridMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Positions of every cell of the given type, in row-major order
+    pub fn cells_of_type(&self, cell_type: T) -> impl Iterator<Item = (usize, usize)> {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| **cell == cell_type)
+            .map(|(index, _)| (index / self.n_columns, index % self.n_columns))
+    }
+
+    pub fn count(&self, cell_type: T) -> usize {
+        self.cells_of_type(cell_type).count()
+    }
+
+    fn is_within_dimensions(&self, row: isize, col: isize) -> bool {
+        GridMode::Bounded
+            .wrap_position(self.dimensions(), row, col)
+            .is_some()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        match row < self.n_rows && col < self.n_columns {
+            true => Some(&self.data[self.index(row, col)]),
+            false => None,
+        }
+    }
+
+    /// Like `get` with signed coordinates, which wrap around in toroidal mode
+    pub fn get_signed(&self, row: isize, col: isize) -> Option<&T> {
+        self.mode
+            .wrap_position(self.dimensions(), row, col)
+            .map(|(row, col)| &self.data[self.index(row, col)])
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, cell_type: T) -> Result<(), OutOfBoundsError> {
+        Self::check_index(row, self.n_rows)?;
+        Self::check_index(col, self.n_columns)?;
+
+        let index = self.index(row, col);
+        self.data[index] = cell_type;
+
+        Ok(())
+    }
+
+    /// Positions around the given one that exist in the grid mode, wrapping around the edges
+    /// of a toroidal grid. Lazily computed, without allocating
+    pub fn neighbors(
+        &self,
+        row: usize,
+        col: usize,
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        adjacency.neighbor_positions(self.mode, self.dimensions(), row, col)
+    }
+
+    /// Cells of `target_type` at each of the `kernel_offsets` from every cell, following the
+    /// grid mode. `Adjacency::offsets` as the kernel gives the usual neighbor counts
+    pub fn convolve_neighbor_count(
+        &self,
+        target_type: T,
+        kernel_offsets: &[[isize; 2]],
+    ) -> Vec<Vec<usize>> {
+        (0..self.n_rows)
+            .map(|row| {
+                (0..self.n_columns)
+                    .map(|col| {
+                        kernel_offsets
+                            .iter()
+                            .filter(|[row_offset, col_offset]| {
+                                self.get_signed(
+                                    row as isize + row_offset,
+                                    col as isize + col_offset,
+                                ) == Some(&target_type)
+                            })
+                            .count()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Top-left positions where the whole pattern matches cell by cell, in row-major order.
+    /// An empty pattern or one bigger than the grid is never found
+    pub fn find_pattern(&self, pattern: &Grid<T>) -> Vec<(usize, usize)> {
+        if pattern.n_rows == 0
+            || pattern.n_columns == 0
+            || pattern.n_rows > self.n_rows
+            || pattern.n_columns > self.n_columns
+        {
+            return Vec::new();
+        }
+
+        let positions = (0..=self.n_rows - pattern.n_rows)
+            .flat_map(|row| (0..=self.n_columns - pattern.n_columns).map(move |col| (row, col)));
+
+        positions
+            .filter(|&(row, col)| {
+                pattern
+                    .rows()
+                    .enumerate()
+                    .all(|(pattern_row, pattern_cells)| {
+                        let start = self.index(row + pattern_row, col);
+
+                        self.data[start..start + pattern.n_columns] == *pattern_cells
+                    })
+            })
+            .collect()
+    }
+
+    fn check_index(index: usize, len: usize) -> Result<(), OutOfBoundsError> {
+        match index < len {
+            true => Ok(()),
+            false => Err(OutOfBoundsError { index, len }),
+        }
+    }
+
+    pub fn swap_rows(&mut self, row_a: usize, row_b: usize) -> Result<(), OutOfBoundsError> {
+        Self::check_index(row_a, self.n_rows)?;
+        Self::check_index(row_b, self.n_rows)?;
+
+        for col in 0..self.n_columns {
+            let (index_a, index_b) = (self.index(row_a, col), self.index(row_b, col));
+            self.data.swap(index_a, index_b);
+        }
+
+        Ok(())
+    }
+
+    pub fn swap_columns(&mut self, col_a: usize, col_b: usize) -> Result<(), OutOfBoundsError> {
+        Self::check_index(col_a, self.n_columns)?;
+        Self::check_index(col_b, self.n_columns)?;
+
+        for row in 0..self.n_rows {
+            let (index_a, index_b) = (self.index(row, col_a), self.index(row, col_b));
+            self.data.swap(index_a, index_b);
+        }
+
+        Ok(())
+    }
+
+    /// Copy of the grid with each row reversed, left to right
+    pub fn flip_horizontal(&self) -> Self {
+        self.with_data(
+            self.rows()
+                .flat_map(|row| row.iter().rev().cloned())
+                .collect(),
+        )
+    }
+
+    /// Copy of the grid with the rows order reversed, top to bottom
+    pub fn flip_vertical(&self) -> Self {
+        self.with_data(self.rows().rev().flatten().cloned().collect())
+    }
+
+    /// New grid with the same dimensions and settings but different cells
+    fn with_data(&self, data: Vec<T>) -> Self {
+        debug_assert_eq!(data.len(), self.data.len());
+
+        Self {
+            n_rows: self.n_rows,
+            n_columns: self.n_columns,
+            data,
+            mode: self.mode,
+            removed_cell: self.removed_cell.clone(),
+        }
+    }
+
+    /// Render the grid one row per line, using custom chars for each cell
+    pub fn render_with(&self, cell_char: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&cell_char).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Main diagonal for offset 0, positive offsets move right and negative ones move down
+    pub fn major_diagonal_iter(&self, offset: isize) -> impl Iterator<Item = &T> {
+        (0..self.n_rows as isize)
+            .map(move |row| (row, row + offset))
+            .filter(|&(row, col)| self.is_within_dimensions(row, col))
+            .map(|(row, col)| &self.data[self.index(row as usize, col as usize)])
+    }
+
+    /// Anti-diagonal (top-right to bottom-left) for offset 0, positive offsets move right and negative ones move left
+    pub fn minor_diagonal_iter(&self, offset: isize) -> impl Iterator<Item = &T> {
+        let last_col = self.n_columns as isize - 1;
+
+        (0..self.n_rows as isize)
+            .map(move |row| (row, last_col - row + offset))
+            .filter(|&(row, col)| self.is_within_dimensions(row, col))
+            .map(|(row, col)| &self.data[self.index(row as usize, col as usize)])
+    }
+
+    /// Parse the rows of chars into cells with `cell_from_char`, which gives `None` for invalid
+    /// chars. Same format rules as `from_str`, blank input giving an empty 0x0 grid
+    pub fn parse_with(
+        value: &str,
+        cell_from_char: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, GridParseError> {
+        let mut rows_parser = RowsParser::default();
+        let mut grid_data: Vec<T> = Vec::new();
+        let mut n_rows = 0;
+
+        for (row, line) in value.trim().lines().enumerate() {
+            grid_data.extend(rows_parser.parse_row(row, line, &cell_from_char)?);
+            n_rows += 1;
+        }
+
+        let n_columns = grid_data.len().checked_div(n_rows).unwrap_or(0);
+
+        Ok(Self::new(n_rows, n_columns, grid_data))
+    }
+}
+
+impl Grid {
+    /// Removing a paper roll leaves a `Removed` marker instead of an `Empty` cell
+    pub fn with_removed_markers(mut self, removed_markers: bool) -> Self {
+        self.removed_cell = removed_markers.then_some(GridType::Removed);
+        self
+    }
+
+    pub fn snapshot(&self) -> GridSnapshot {
+        GridSnapshot {
+            n_rows: self.n_rows,
+            n_columns: self.n_columns,
+            data: self.data.clone(),
+        }
+    }
+
+    /// Bring back the cells of the snapshot, keeping the current mode and removed markers.
+    /// Neighbor count maps computed before are stale afterwards
+    pub fn restore(&mut self, snapshot: &GridSnapshot) {
+        self.n_rows = snapshot.n_rows;
+        self.n_columns = snapshot.n_columns;
+        self.data.clone_from(&snapshot.data);
+    }
+
+    /// Move every paper roll with an empty cell below one row down at once, so a roll resting
+    /// on another one that falls only follows it on the next step. Walls and the bottom row stop
+    /// the rolls, even on toroidal grids. Returns the number of rolls that moved
+    pub fn simulate_gravity(&mut self) -> usize {
+        let is_free = |cell: &GridType| matches!(cell, GridType::Empty | GridType::Removed);
+
+        // Decided on the cells before the step, so every roll moves at most once
+        let falling_rolls: Vec<usize> = (0..self.data.len().saturating_sub(self.n_columns))
+            .filter(|&index| {
+                self.data[index] == GridType::PaperRoll
+                    && is_free(&self.data[index + self.n_columns])
+            })
+            .collect();
+
+        for &index in falling_rolls.iter() {
+            self.data[index] = GridType::Empty;
+            self.data[index + self.n_columns] = GridType::PaperRoll;
+        }
+
+        falling_rolls.len()
+    }
+
+    /// Apply gravity until no paper roll moves, returning the number of single row moves
+    pub fn settle_gravity(&mut self) -> usize {
+        let mut moves = 0;
+
+        loop {
+            match self.simulate_gravity() {
+                0 => return moves,
+                step_moves => moves += step_moves,
+            }
+        }
+    }
+
+    /// No paper roll left in the grid
+    pub fn is_cleared(&self) -> bool {
+        self.cells_of_type(GridType::PaperRoll).next().is_none()
+    }
+
+    /// Smallest box containing every paper roll, as `(min_row, min_col, max_row, max_col)`
+    pub fn bounding_box_of_rolls(&self) -> Option<(usize, usize, usize, usize)> {
+        self.cells_of_type(GridType::PaperRoll)
+            .fold(None, |bounding_box, (row, col)| match bounding_box {
+                None => Some((row, col, row, col)),
+                Some((min_row, min_col, max_row, max_col)) => Some((
+                    min_row.min(row),
+                    min_col.min(col),
+                    max_row.max(row),
+                    max_col.max(col),
+                )),
+            })
+    }
+
+    fn adjacent_cells(
+        &self,
+        row: usize,
+        col: usize,
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = &GridType> {
+        self.neighbors(row, col, adjacency)
+            .map(|(row, col)| &self.data[self.index(row, col)])
+    }
+
+    fn count_adjacent_of_type(&self, row: usize, col: usize, cell_type: GridType) -> usize {
+        self.adjacent_cells(row, col, Adjacency::Eight)
+            .filter(|&&cell| cell == cell_type)
+            .count()
+    }
+
+    fn count_isolated_of_type(&self, cell_type: GridType) -> usize {
+        self.cells_of_type(cell_type)
+            .filter(|&(row, col)| self.count_adjacent_of_type(row, col, cell_type) == 0)
+            .count()
+    }
+
+    /// Paper rolls without any adjacent paper roll
+    pub fn count_isolated_paper_rolls(&self) -> usize {
+        self.count_isolated_of_type(GridType::PaperRoll)
+    }
+
+    /// Empty cells without any adjacent empty cell
+    pub fn count_isolated_empty(&self) -> usize {
+        self.count_isolated_of_type(GridType::Empty)
+    }
+
+    /// Adjacent paper rolls count of every cell, so the accessibility checks don't need to scan
+    /// them again. Counted like `AccessibilityScan` does without a map, walls aside
+    pub fn compute_neighbor_count_map(&self) -> Vec<Vec<u8>> {
+        (0..self.n_rows)
+            .map(|row| {
+                (0..self.n_columns)
+                    .map(|col| {
+                        self.count_adjacent_paper_rolls_up_to(
+                            row,
+                            col,
+                            Adjacency::Eight,
+                            usize::MAX,
+                        ) as u8
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Groups of connected paper rolls, ordered by their first cell with the cells in row-major
+    /// order. Flood filled with an explicit stack so long snake-shaped groups don't overflow
+    pub fn roll_components(&self, adjacency: Adjacency) -> Vec<Vec<(usize, usize)>> {
+        let mut visited = vec![vec![false; self.n_columns]; self.n_rows];
+        let mut components = Vec::new();
+
+        for (row, col) in self.cells_of_type(GridType::PaperRoll) {
+            if visited[row][col] {
+                continue;
+            }
+
+            visited[row][col] = true;
+
+            let mut component = Vec::new();
+            let mut pending = Vec::from([(row, col)]);
+
+            while let Some((row, col)) = pending.pop() {
+                component.push((row, col));
+
+                for (row, col) in self.neighbors(row, col, adjacency) {
+                    if !visited[row][col] && self.is_paper_roll(row, col) {
+                        visited[row][col] = true;
+                        pending.push((row, col));
+                    }
+                }
+            }
+
+            component.sort_unstable();
+            components.push(component);
+        }
+
+        components
+    }
+
+    pub fn count_roll_components(&self, adjacency: Adjacency) -> usize {
+        self.roll_components(adjacency).len()
+    }
+
+    pub fn diff(&self, other: &Grid) -> Result<GridDiff, DimensionMismatch> {
+        if (self.n_rows, self.n_columns) != (other.n_rows, other.n_columns) {
+            return Err(DimensionMismatch {
+                expected: (self.n_rows, self.n_columns),
+                found: (other.n_rows, other.n_columns),
+            });
+        }
+
+        let mut grid_diff = GridDiff {
+            only_in_self: Vec::new(),
+            only_in_other: Vec::new(),
+        };
+
+        for row in 0..self.n_rows {
+            for col in 0..self.n_columns {
+                match (self.is_paper_roll(row, col), other.is_paper_roll(row, col)) {
+                    (true, false) => grid_diff.only_in_self.push((row, col)),
+                    (false, true) => grid_diff.only_in_other.push((row, col)),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(grid_diff)
+    }
+}
+
+impl GridStore for Grid {
+    fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    fn n_columns(&self) -> usize {
+        self.n_columns
+    }
+
+    fn mode(&self) -> GridMode {
+        self.mode
+    }
+
+    fn is_paper_roll(&self, row: usize, col: usize) -> bool {
+        self.get(row, col) == Some(&GridType::PaperRoll)
+    }
+
+    fn is_occupied(&self, row: usize, col: usize) -> bool {
+        self.get(row, col).is_some_and(GridType::is_occupied)
+    }
+
+    fn get_signed(&self, row: isize, col: isize) -> Option<GridType> {
+        WarehouseGrid::get_signed(self, row, col).copied()
+    }
+
+    fn occupied_neighbor_count(&self, row: usize, col: usize, adjacency: Adjacency) -> usize {
+        self.adjacent_cells(row, col, adjacency)
+            .filter(|cell| cell.is_occupied())
+            .count()
+    }
+
+    fn count_adjacent_occupied_up_to(
+        &self,
+        row: usize,
+        col: usize,
+        adjacency: Adjacency,
+        limit: usize,
+    ) -> usize {
+        self.adjacent_cells(row, col, adjacency)
+            .filter(|cell| cell.is_occupied())
+            .take(limit)
+            .count()
+    }
+
+    fn remove_item(&mut self, row: usize, col: usize) -> GridType {
+        let previous = self.get(row, col).copied().unwrap_or(GridType::Empty);
+
+        if previous == GridType::PaperRoll {
+            let index = self.index(row, col);
+
+            self.data[index] = self.removed_cell.unwrap_or(GridType::Empty);
+        }
+
+        previous
+    }
+
+    fn paper_rolls_in_rows(&self, rows: Range<usize>) -> impl Iterator<Item = (usize, usize)> {
+        rows.flat_map(move |row| (0..self.n_columns).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.is_paper_roll(row, col))
+    }
+
+    fn paper_rolls(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.cells_of_type(GridType::PaperRoll)
+    }
+}
+
+impl Grid {
+    /// Grid with paper rolls at the given positions and every other cell empty. Repeated
+    /// positions are only an error when `reject_duplicates` is set
+    pub fn from_coords(
+        n_rows: usize,
+        n_columns: usize,
+        rolls: impl IntoIterator<Item = (usize, usize)>,
+        reject_duplicates: bool,
+    ) -> Result<Grid, GridBuildError> {
+        let mut grid = Self::new(n_rows, n_columns, vec![GridType::Empty; n_rows * n_columns]);
+
+        for (row, col) in rolls {
+            if row >= n_rows || col >= n_columns {
+                return Err(GridBuildError::OutOfBounds { row, col });
+            }
+
+            let index = grid.index(row, col);
+
+            if reject_duplicates && grid.data[index] == GridType::PaperRoll {
+                return Err(GridBuildError::DuplicateRoll { row, col });
+            }
+
+            grid.data[index] = GridType::PaperRoll;
+        }
+
+        Ok(grid)
+    }
+
+    /// Inverse of `from_coords`, in row-major order
+    pub fn roll_positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.cells_of_type(GridType::PaperRoll)
+    }
+
+    /// Like `from_str`, but rows shorter than the longest one are padded with empty cells
+    /// instead of rejected, for hand-written grids leaving out their trailing empty cells
+    pub fn parse_padded(value: &str) -> Result<Grid, GridParseError> {
+        let rows = value
+            .trim()
+            .lines()
+            .enumerate()
+            .map(|(row, line)| parse_cells(row, line, GridType::from_char))
+            .collect::<Result<Vec<Vec<GridType>>, GridParseError>>()?;
+
+        let n_columns = rows
+            .iter()
+            .map(|row_data| row_data.len())
+            .max()
+            .unwrap_or(0);
+
+        let grid_data = rows
+            .iter()
+            .flat_map(|row_data| {
+                let padding = n_columns - row_data.len();
+
+                row_data
+                    .iter()
+                    .copied()
+                    .chain(std::iter::repeat_n(GridType::Empty, padding))
+            })
+            .collect();
+
+        Ok(Self::new(rows.len(), n_columns, grid_data))
+    }
+
+    /// Parse line by line, only keeping the current line as text. Same format as `from_str`
+    pub fn from_reader(mut reader: impl BufRead) -> Result<Grid, GridParseError> {
+        let mut rows_parser = RowsParser::default();
+        let mut grid_data: Vec<GridType> = Vec::new();
+        let mut n_rows = 0;
+        let mut line = String::new();
+
+        // Blank lines only matter when followed by another row, like when trimming a string
+        let mut blank_lines = 0;
+
+        loop {
+            line.clear();
+
+            let row = n_rows + blank_lines;
+            let read_bytes = reader
+                .read_line(&mut line)
+                .map_err(|error| GridParseError::Io {
+                    row,
+                    kind: error.kind(),
+                })?;
+
+            if read_bytes == 0 {
+                break;
+            }
+
+            if line.trim().is_empty() {
+                if n_rows > 0 {
+                    blank_lines += 1;
+                }
+
+                continue;
+            }
+
+            if blank_lines > 0 {
+                rows_parser.parse_row(n_rows, "", GridType::from_char)?;
+            }
+
+            grid_data.extend(rows_parser.parse_row(row, &line, GridType::from_char)?);
+            n_rows += 1;
+        }
+
+        let n_columns = rows_parser.expected_width.unwrap_or(0);
+
+        Ok(Self::new(n_rows, n_columns, grid_data))
+    }
+}
+
+impl FromStr for Grid {
+    type Err = GridParseError;
+
+    /// Blank input gives an empty 0x0 grid
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(value, GridType::from_char)
+    }
+}
+
+/// Parse the grid one row at a time, checking every row against the first one's width. Both LF
+/// and CRLF line endings are accepted
+pub(crate) fn parse_rows(
+    value: &str,
+) -> impl Iterator<Item = Result<Vec<GridType>, GridParseError>> {
+    let mut rows_parser = RowsParser::default();
+
+    value
+        .trim()
+        .lines()
+        .enumerate()
+        .map(move |(row, line)| rows_parser.parse_row(row, line, GridType::from_char))
+}
+
+fn parse_cells<T>(
+    row: usize,
+    line: &str,
+    cell_from_char: impl Fn(char) -> Option<T>,
+) -> Result<Vec<T>, GridParseError> {
+    line.trim()
+        .chars()
+        .enumerate()
+        .map(|(col, char)| {
+            cell_from_char(char).ok_or(GridParseError::InvalidCharacter { char, row, col })
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct RowsParser {
+    expected_width: Option<usize>,
+}
+
+impl RowsParser {
+    fn parse_row<T>(
+        &mut self,
+        row: usize,
+        line: &str,
+        cell_from_char: impl Fn(char) -> Option<T>,
+    ) -> Result<Vec<T>, GridParseError> {
+        let row_data = parse_cells(row, line, cell_from_char)?;
+
+        let expected = *self.expected_width.get_or_insert(row_data.len());
+
+        if row_data.len() != expected {
+            return Err(GridParseError::InconsistentRowWidth {
+                row,
+                width: row_data.len(),
+                expected,
+            });
+        }
+
+        Ok(row_data)
+    }
+}
+
+impl From<&str> for Grid {
+    fn from(value: &str) -> Self {
+        value
+            .parse()
+            .unwrap_or_else(|error| panic!("Invalid grid: {}", error))
+    }
+}
+
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render_with(|cell| char::from(cell)))
+    }
+}
+
+/// Rendered as the grid itself, so failed equality assertions show both grids as they're drawn
+impl fmt::Debug for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Grid {}x{} ({:?})",
+            self.n_rows, self.n_columns, self.mode
+        )?;
+        write!(f, "{}", self)
+    }
+}
+
+/// Same dimensions and cells, regardless of the mode and removed markers setting
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.n_rows == other.n_rows && self.n_columns == other.n_columns && self.data == other.data
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+/// Hashes the same fields compared by `PartialEq`, so equal grids share a `HashMap` entry
+impl<T: Hash> Hash for Grid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.n_rows.hash(state);
+        self.n_columns.hash(state);
+        self.data.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        io::Cursor,
+    };
+
+    use super::*;
+    use crate::{AccessibilityScan, RemoveError, RemovedKind, generator::random_grid};
+
+    #[test]
+    fn test_swap_rows_and_columns() {
+        let data = "@..\n.@.\n..@\n@@.";
+        let mut grid = Grid::from(data);
+
+        grid.swap_rows(0, 1).unwrap();
+        assert_eq!(grid.to_string(), ".@.\n@..\n..@\n@@.");
+
+        grid.swap_rows(0, 1).unwrap();
+        assert_eq!(grid.to_string(), data);
+
+        grid.swap_columns(0, 2).unwrap();
+        assert_eq!(grid.to_string(), "..@\n.@.\n@..\n.@@");
+
+        grid.swap_columns(0, 2).unwrap();
+        assert_eq!(grid.to_string(), data);
+
+        assert_eq!(
+            grid.swap_rows(0, 4),
+            Err(OutOfBoundsError { index: 4, len: 4 })
+        );
+        assert_eq!(
+            grid.swap_columns(3, 0),
+            Err(OutOfBoundsError { index: 3, len: 3 })
+        );
+        assert_eq!(grid.to_string(), data);
+    }
+
+    #[test]
+    fn test_checked_accessors() {
+        let mut grid = Grid::from("@..\n.@#");
+
+        assert_eq!(grid.get(0, 0), Some(&GridType::PaperRoll));
+        assert_eq!(grid.get(1, 2), Some(&GridType::Wall));
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+
+        assert_eq!(grid.get_signed(1, 1), Some(&GridType::PaperRoll));
+        assert_eq!(grid.get_signed(-1, 0), None);
+        assert_eq!(grid.get_signed(0, -1), None);
+        assert_eq!(grid.get_signed(2, 0), None);
+        assert_eq!(grid.get_signed(0, 3), None);
+
+        assert_eq!(grid.set(0, 1, GridType::PaperRoll), Ok(()));
+        assert_eq!(grid.to_string(), "@@.\n.@#");
+        assert_eq!(
+            grid.set(2, 0, GridType::PaperRoll),
+            Err(OutOfBoundsError { index: 2, len: 2 })
+        );
+        assert_eq!(
+            grid.set(0, 3, GridType::PaperRoll),
+            Err(OutOfBoundsError { index: 3, len: 3 })
+        );
+        assert_eq!(grid.to_string(), "@@.\n.@#");
+
+        let grid = grid.with_mode(GridMode::Toroidal);
+
+        assert_eq!(grid.get_signed(-1, -1), Some(&GridType::Wall));
+        assert_eq!(grid.get_signed(2, 3), Some(&GridType::PaperRoll));
+        assert_eq!(grid.get(2, 3), None);
+
+        let grid = Grid::from("").with_mode(GridMode::Toroidal);
+
+        assert_eq!(grid.get_signed(0, 0), None);
+    }
+
+    #[test]
+    fn test_flip_grid() {
+        let data = "@..\n.@.\n..@\n@@.";
+        let grid = Grid::from(data);
+
+        assert_eq!(grid.flip_horizontal().to_string(), "..@\n.@.\n@..\n.@@");
+        assert_eq!(grid.flip_vertical().to_string(), "@@.\n..@\n.@.\n@..");
+
+        assert_eq!(grid.flip_horizontal().flip_horizontal().to_string(), data);
+        assert_eq!(grid.flip_vertical().flip_vertical().to_string(), data);
+
+        // Flipping both ways is a half turn, reversing the rendered grid
+        let half_turn = data.chars().rev().collect::<String>();
+
+        assert_eq!(
+            grid.flip_horizontal().flip_vertical().to_string(),
+            half_turn
+        );
+        assert_eq!(
+            grid.flip_vertical().flip_horizontal().to_string(),
+            half_turn
+        );
+
+        let grid = Grid::from("@.").with_mode(GridMode::Toroidal);
+
+        assert_eq!(grid.flip_horizontal().mode(), GridMode::Toroidal);
+        assert_eq!(grid.flip_horizontal().n_columns(), 2);
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        let grid: Grid = "..@\n@@.\n".parse().unwrap();
+
+        assert_eq!(grid.n_rows, 2);
+        assert_eq!(grid.n_columns, 3);
+        assert!(grid.is_paper_roll(0, 2));
+        assert!(!grid.is_paper_roll(1, 2));
+
+        let grid: Grid = "  \n \n".parse().unwrap();
+
+        assert_eq!(grid.n_rows, 0);
+        assert_eq!(grid.n_columns, 0);
+    }
+
+    #[test]
+    fn test_walls_and_removed_markers() {
+        let mut grid = Grid::from("@#.\n.@@");
+
+        assert!(grid.is_occupied(0, 1));
+        assert!(!grid.is_paper_roll(0, 1));
+        assert_eq!(grid.remove_item(0, 1), GridType::Wall);
+        assert_eq!(grid.remove_item(0, 2), GridType::Empty);
+        assert_eq!(grid.remove_item(0, 0), GridType::PaperRoll);
+        assert_eq!(grid.remove_item(0, 0), GridType::Empty);
+        assert_eq!(grid.remove_item(5, 0), GridType::Empty);
+        assert_eq!(grid.to_string(), ".#.\n.@@");
+
+        let mut grid = grid.with_removed_markers(true);
+
+        assert_eq!(grid.remove_item(1, 2), GridType::PaperRoll);
+        assert_eq!(grid.remove_item(1, 2), GridType::Removed);
+        assert!(!grid.is_occupied(1, 2));
+        assert_eq!(grid.to_string(), ".#.\n.@x");
+        assert_eq!(grid.count_paper_rolls(), 1);
+    }
+
+    #[test]
+    fn test_parse_grid_from_reader() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let grid = Grid::from_reader(Cursor::new(data)).unwrap();
+        let parsed_grid = Grid::from(data);
+
+        assert_eq!(grid.n_rows, parsed_grid.n_rows);
+        assert_eq!(grid.n_columns, parsed_grid.n_columns);
+        assert_eq!(grid.data, parsed_grid.data);
+
+        let grid = Grid::from_reader(Cursor::new("\n\n..@\r\n@@.\r\n\n")).unwrap();
+        assert_eq!(grid.to_string(), "..@\n@@.");
+
+        let grid = Grid::from_reader(Cursor::new("")).unwrap();
+        assert_eq!((grid.n_rows, grid.n_columns), (0, 0));
+
+        // Third line, with 0-based rows like the other errors
+        assert_eq!(
+            Grid::from_reader(Cursor::new("..@\n@@.\n@@\n...")).err(),
+            Some(GridParseError::InconsistentRowWidth {
+                row: 2,
+                width: 2,
+                expected: 3
+            })
+        );
+
+        for data in ["..@\n\n@@.", "..@\n@x.", "\n@@\n@@@"] {
+            assert_eq!(
+                Grid::from_reader(Cursor::new(data)).err(),
+                data.parse::<Grid>().err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_grid_crlf() {
+        let crlf_grid = Grid::from("..@\r\n@@.\r\n.@.\r\n");
+        let lf_grid = Grid::from("..@\n@@.\n.@.\n");
+
+        assert_eq!(crlf_grid.n_columns, 3);
+        assert_eq!(crlf_grid.data, lf_grid.data);
+        assert_eq!(crlf_grid.to_string(), lf_grid.to_string());
+    }
+
+    #[test]
+    fn test_parse_grid_errors() {
+        assert_eq!(
+            "..@\n@@\n...".parse::<Grid>().err(),
+            Some(GridParseError::InconsistentRowWidth {
+                row: 1,
+                width: 2,
+                expected: 3
+            })
+        );
+
+        assert_eq!(
+            "..@\n@x.".parse::<Grid>().err(),
+            Some(GridParseError::InvalidCharacter {
+                char: 'x',
+                row: 1,
+                col: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_grid_padded() {
+        let grid = Grid::parse_padded("..@\n@@\n\n@\n.@.#").unwrap();
+
+        assert_eq!((grid.n_rows, grid.n_columns), (5, 4));
+        assert_eq!(grid.to_string(), "..@.\n@@..\n....\n@...\n.@.#");
+        assert!(grid.is_within_bounds(1, 3));
+        assert_eq!(grid.get(1, 3), Some(&GridType::Empty));
+        assert_eq!(grid.count_paper_rolls(), 5);
+
+        // Rectangular grids are the same as with the strict parser
+        let data = "..@\r\n@@.\r\n.@.";
+        assert_eq!(Grid::parse_padded(data), data.parse::<Grid>());
+
+        // The strict parser still rejects them, and invalid chars are rejected by both
+        assert_eq!(
+            "..@\n@@".parse::<Grid>().err(),
+            Some(GridParseError::InconsistentRowWidth {
+                row: 1,
+                width: 2,
+                expected: 3
+            })
+        );
+        assert_eq!(
+            Grid::parse_padded("..@\n@x").err(),
+            Some(GridParseError::InvalidCharacter {
+                char: 'x',
+                row: 1,
+                col: 1
+            })
+        );
+        assert_eq!(Grid::parse_padded("  \n"), Ok(Grid::from("")));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid grid: Inconsistent row width (row 1 has 2, expected 3)")]
+    fn test_parse_grid_ragged_panic() {
+        let _ = Grid::from("..@\n@@");
+    }
+
+    #[test]
+    fn test_display_grid() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let expected_data = data
+            .trim()
+            .lines()
+            .map(|line| line.trim())
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        let grid = Grid::from(data);
+
+        assert_eq!(grid.to_string(), expected_data);
+        assert_eq!(
+            grid.render_with(|cell| match cell {
+                GridType::PaperRoll => '#',
+                _ => ' ',
+            })
+            .lines()
+            .next(),
+            Some("  ## #### ")
+        );
+    }
+
+    #[test]
+    fn test_toroidal_mode() {
+        let grid = Grid::from(
+            r#"
+            @...
+            ....
+            ...."#,
+        )
+        .with_mode(GridMode::Toroidal);
+
+        assert!(grid.is_within_bounds(-1, -1));
+        assert_eq!(grid.is_within_bounds_wrap(-1, -1), (2, 3));
+        assert_eq!(grid.is_within_bounds_wrap(3, 4), (0, 0));
+
+        let wrapped_neighbors: HashSet<(usize, usize)> = Adjacency::Eight
+            .offsets()
+            .iter()
+            .map(|[row_offset, col_offset]| grid.is_within_bounds_wrap(*row_offset, *col_offset))
+            .collect();
+
+        assert_eq!(wrapped_neighbors.len(), 8);
+        assert!(wrapped_neighbors.contains(&(2, 3)));
+        assert!(!wrapped_neighbors.contains(&(0, 0)));
+    }
+
+    #[test]
+    fn test_cells_of_type() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let grid = Grid::from(data);
+
+        assert_eq!(
+            grid.cells_of_type(GridType::PaperRoll).count(),
+            grid.count_paper_rolls()
+        );
+        assert_eq!(grid.cells_of_type(GridType::Empty).count(), 29);
+        assert_eq!(grid.cells_of_type(GridType::Wall).count(), 0);
+
+        let grid = Grid::from(".@\n#@");
+
+        assert_eq!(
+            grid.cells_of_type(GridType::PaperRoll)
+                .collect::<Vec<(usize, usize)>>(),
+            Vec::from([(0, 1), (1, 1)])
+        );
+        assert_eq!(
+            grid.cells_of_type(GridType::Wall)
+                .collect::<Vec<(usize, usize)>>(),
+            Vec::from([(1, 0)])
+        );
+    }
+
+    #[test]
+    fn test_isolated_cells() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let grid = Grid::from(data);
+
+        assert_eq!(grid.count_isolated_paper_rolls(), 0);
+        assert_eq!(grid.count_isolated_empty(), 4);
+
+        let data = r#"
+            @.@.@
+            .....
+            @.@.@"#;
+
+        let grid = Grid::from(data);
+
+        assert_eq!(grid.count_isolated_paper_rolls(), 6);
+        assert_eq!(grid.count_isolated_empty(), 0);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from("@@@@\n@@@@\n@@@@\n@@@@");
+
+        let neighbors = |row, col, adjacency| -> HashSet<(usize, usize)> {
+            grid.neighbors(row, col, adjacency).collect()
+        };
+
+        // Corner, edge and interior cells
+        assert_eq!(
+            neighbors(0, 0, Adjacency::Eight),
+            HashSet::from([(0, 1), (1, 0), (1, 1)])
+        );
+        assert_eq!(
+            neighbors(0, 1, Adjacency::Eight),
+            HashSet::from([(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)])
+        );
+        assert_eq!(neighbors(1, 2, Adjacency::Eight).len(), 8);
+
+        assert_eq!(
+            neighbors(3, 3, Adjacency::Four),
+            HashSet::from([(2, 3), (3, 2)])
+        );
+        assert_eq!(
+            neighbors(3, 1, Adjacency::Four),
+            HashSet::from([(2, 1), (3, 0), (3, 2)])
+        );
+        assert_eq!(
+            neighbors(2, 1, Adjacency::Four),
+            HashSet::from([(1, 1), (3, 1), (2, 0), (2, 2)])
+        );
+
+        let grid = Grid::from("@#.\n.@.\n...");
+
+        assert_eq!(grid.occupied_neighbor_count(0, 0, Adjacency::Eight), 2);
+        assert_eq!(grid.occupied_neighbor_count(0, 0, Adjacency::Four), 1);
+        assert_eq!(grid.occupied_neighbor_count(2, 2, Adjacency::Eight), 1);
+        assert_eq!(grid.occupied_neighbor_count(2, 2, Adjacency::Four), 0);
+
+        // Stops counting at the limit
+        let full_grid = Grid::from("@@@\n@@@\n@@@");
+
+        assert_eq!(
+            full_grid.count_adjacent_occupied_up_to(1, 1, Adjacency::Eight, 4),
+            4
+        );
+        assert_eq!(
+            full_grid.count_adjacent_occupied_up_to(1, 1, Adjacency::Eight, 9),
+            8
+        );
+        assert_eq!(
+            full_grid.count_adjacent_occupied_up_to(1, 1, Adjacency::Eight, 0),
+            0
+        );
+        assert_eq!(
+            grid.count_adjacent_occupied_up_to(0, 0, Adjacency::Eight, 4),
+            2
+        );
+
+        // Toroidal grids wrap the corner to the opposite ones
+        let grid = grid.with_mode(GridMode::Toroidal);
+
+        assert_eq!(grid.neighbors(0, 0, Adjacency::Eight).count(), 8);
+        assert_eq!(grid.occupied_neighbor_count(2, 2, Adjacency::Eight), 3);
+    }
+
+    #[test]
+    fn test_toroidal_neighbors_on_narrow_grids() {
+        let neighbors = |grid: &Grid, row, col, adjacency| {
+            let mut neighbors: Vec<(usize, usize)> = grid.neighbors(row, col, adjacency).collect();
+            neighbors.sort_unstable();
+
+            neighbors
+        };
+
+        // Wrapping both ways reaches the other 3 cells once each
+        let grid = Grid::from("@@\n@@").with_mode(GridMode::Toroidal);
+
+        assert_eq!(
+            neighbors(&grid, 0, 0, Adjacency::Eight),
+            Vec::from([(0, 1), (1, 0), (1, 1)])
+        );
+        assert_eq!(
+            neighbors(&grid, 1, 1, Adjacency::Four),
+            Vec::from([(0, 1), (1, 0)])
+        );
+        assert_eq!(grid.occupied_neighbor_count(0, 0, Adjacency::Eight), 3);
+
+        // A single row wraps the vertical offsets onto the cell itself
+        let grid = Grid::from("@@@@").with_mode(GridMode::Toroidal);
+
+        assert_eq!(
+            neighbors(&grid, 0, 0, Adjacency::Eight),
+            Vec::from([(0, 1), (0, 3)])
+        );
+
+        let grid = Grid::from("@").with_mode(GridMode::Toroidal);
+
+        assert_eq!(grid.neighbors(0, 0, Adjacency::Eight).count(), 0);
+        assert_eq!(
+            GridStore::neighbors(&grid, 0, 0, Adjacency::Eight).count(),
+            0
+        );
+
+        // A lone roll has nothing around it, so it's accessible
+        assert_eq!(
+            AccessibilityScan::new(&grid)
+                .accessible_paper_rolls()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_gravity() {
+        let mut grid = Grid::from("@..\n...\n...\n...");
+
+        assert_eq!(grid.simulate_gravity(), 1);
+        assert_eq!(grid.to_string(), "...\n@..\n...\n...");
+        assert_eq!(grid.settle_gravity(), 2);
+        assert_eq!(grid.to_string(), "...\n...\n...\n@..");
+        assert_eq!(grid.simulate_gravity(), 0);
+        assert_eq!(grid.settle_gravity(), 0);
+
+        // A stacked roll only starts falling once the one below it has moved
+        let mut grid = Grid::from("@.\n@.\n..\n..");
+
+        assert_eq!(grid.simulate_gravity(), 1);
+        assert_eq!(grid.to_string(), "@.\n..\n@.\n..");
+        assert_eq!(grid.settle_gravity(), 3);
+        assert_eq!(grid.to_string(), "..\n..\n@.\n@.");
+
+        // Walls hold the rolls, and removed markers are free cells
+        let mut grid = Grid::from("@@@\n.#.\n...").with_removed_markers(true);
+        grid.remove_item(0, 2);
+
+        assert_eq!(grid.settle_gravity(), 2);
+        assert_eq!(grid.to_string(), ".@x\n.#.\n@..");
+        assert_eq!(grid.count_paper_rolls(), 2);
+    }
+
+    #[test]
+    fn test_neighbor_count_map() {
+        let data = r#"
+            @@@.
+            @#@.
+            ...@"#;
+
+        let grid = Grid::from(data);
+
+        assert_eq!(
+            grid.compute_neighbor_count_map(),
+            Vec::from([
+                Vec::from([2, 4, 2, 2]),
+                Vec::from([2, 5, 3, 3]),
+                Vec::from([1, 2, 2, 1])
+            ])
+        );
+
+        let grid = Grid::from("@@\n@@").with_mode(GridMode::Toroidal);
+
+        // Every other cell is a distinct neighbor, counted once
+        assert_eq!(
+            grid.compute_neighbor_count_map(),
+            Vec::from([Vec::from([3, 3]), Vec::from([3, 3])])
+        );
+        assert_eq!(Grid::from("").compute_neighbor_count_map().len(), 0);
+    }
+
+    #[test]
+    fn test_convolve_neighbor_count() {
+        // Without walls only the paper rolls take a neighbor slot
+        for seed in 0..5 {
+            let data = random_grid(15, 20, 30 + seed * 10, seed);
+
+            for mode in [GridMode::Bounded, GridMode::Toroidal] {
+                let grid = Grid::from(data.as_str()).with_mode(mode);
+
+                let neighbor_counts: Vec<Vec<usize>> = grid
+                    .compute_neighbor_count_map()
+                    .into_iter()
+                    .map(|row| row.into_iter().map(usize::from).collect())
+                    .collect();
+
+                assert_eq!(
+                    grid.convolve_neighbor_count(GridType::PaperRoll, Adjacency::Eight.offsets()),
+                    neighbor_counts
+                );
+
+                for (row, counts) in grid
+                    .convolve_neighbor_count(GridType::PaperRoll, Adjacency::Four.offsets())
+                    .iter()
+                    .enumerate()
+                {
+                    for (col, &count) in counts.iter().enumerate() {
+                        assert_eq!(
+                            count,
+                            grid.occupied_neighbor_count(row, col, Adjacency::Four)
+                        );
+                    }
+                }
+            }
+        }
+
+        // Other cell types and kernels, including the cell itself
+        let grid = Grid::from("@#.\n#@.");
+
+        assert_eq!(
+            grid.convolve_neighbor_count(GridType::Wall, Adjacency::Eight.offsets()),
+            Vec::from([Vec::from([2, 1, 1]), Vec::from([1, 2, 1])])
+        );
+        assert_eq!(
+            grid.convolve_neighbor_count(GridType::Empty, &[[0, 0], [0, 1]]),
+            Vec::from([Vec::from([0, 1, 1]), Vec::from([0, 1, 1])])
+        );
+        assert_eq!(
+            grid.convolve_neighbor_count(GridType::PaperRoll, &[]),
+            Vec::from([Vec::from([0, 0, 0]), Vec::from([0, 0, 0])])
+        );
+    }
+
+    #[test]
+    fn test_roll_components() {
+        let data = r#"
+            @@..
+            @@..
+            ..@@
+            ..@@"#;
+
+        let grid = Grid::from(data);
+
+        // Both clusters only touch diagonally
+        assert_eq!(
+            grid.roll_components(Adjacency::Eight),
+            Vec::from([Vec::from([
+                (0, 0),
+                (0, 1),
+                (1, 0),
+                (1, 1),
+                (2, 2),
+                (2, 3),
+                (3, 2),
+                (3, 3)
+            ])])
+        );
+        assert_eq!(
+            grid.roll_components(Adjacency::Four),
+            Vec::from([
+                Vec::from([(0, 0), (0, 1), (1, 0), (1, 1)]),
+                Vec::from([(2, 2), (2, 3), (3, 2), (3, 3)])
+            ])
+        );
+
+        let grid = Grid::from("@..@\n....\n@..@");
+
+        assert_eq!(grid.count_roll_components(Adjacency::Eight), 4);
+        assert_eq!(
+            grid.with_mode(GridMode::Toroidal)
+                .count_roll_components(Adjacency::Eight),
+            1
+        );
+        assert_eq!(Grid::from("...").count_roll_components(Adjacency::Eight), 0);
+    }
+
+    #[test]
+    fn test_snake_roll_component() {
+        // Full rows joined at alternating ends, as a single 10k cells long path
+        let data = (0..99)
+            .map(|row| match row % 4 {
+                0 | 2 => "@".repeat(200),
+                1 => format!("{}@", ".".repeat(199)),
+                _ => format!("@{}", ".".repeat(199)),
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let components = Grid::from(data.as_str()).roll_components(Adjacency::Four);
+
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), 50 * 200 + 49);
+    }
+
+    #[test]
+    fn test_grid_diff() {
+        let grid = Grid::from("@@.\n.@@");
+
+        assert!(grid == Grid::from("@@.\n.@@").with_mode(GridMode::Toroidal));
+        assert!(grid != Grid::from("@@.\n.@."));
+        assert!(grid != Grid::from("@@\n.@"));
+        assert!(grid.diff(&Grid::from("@@.\n.@@")).unwrap().is_empty());
+
+        assert_eq!(
+            grid.diff(&Grid::from("#.@\n.@.")),
+            Ok(GridDiff {
+                only_in_self: Vec::from([(0, 0), (0, 1), (1, 2)]),
+                only_in_other: Vec::from([(0, 2)])
+            })
+        );
+        assert_eq!(
+            grid.diff(&Grid::from("@@\n.@")),
+            Err(DimensionMismatch {
+                expected: (2, 3),
+                found: (2, 2)
+            })
+        );
+        assert_eq!(
+            grid.diff(&Grid::from("@@")).unwrap_err().to_string(),
+            "Expected a 2x3 grid but found a 1x2 one"
+        );
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let grid = Grid::from(data);
+
+        assert_eq!(
+            grid.find_pattern(&Grid::from("@@\n@@")),
+            Vec::from([
+                (1, 0),
+                (1, 1),
+                (1, 8),
+                (2, 2),
+                (2, 3),
+                (3, 3),
+                (3, 4),
+                (4, 3),
+                (4, 4),
+                (4, 5),
+                (6, 7),
+                (6, 8),
+                (7, 2),
+                (7, 3),
+                (7, 6),
+                (7, 7),
+                (8, 4),
+                (8, 5)
+            ])
+        );
+        assert_eq!(
+            grid.find_pattern(&Grid::from(".@.\n@@@")),
+            Vec::from([(6, 2)])
+        );
+        assert_eq!(grid.find_pattern(&Grid::from("@.@.\n@@@@")), Vec::new());
+
+        // The whole grid only matches itself, anything bigger is never found
+        assert_eq!(grid.find_pattern(&Grid::from(data)), Vec::from([(0, 0)]));
+        assert_eq!(
+            Grid::from("@@\n@@").find_pattern(&Grid::from("@@@\n@@@")),
+            Vec::new()
+        );
+        assert_eq!(grid.find_pattern(&Grid::from("")), Vec::new());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trip() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let mut grid = Grid::from(data)
+            .with_mode(GridMode::Toroidal)
+            .with_removed_markers(true);
+
+        let json = grid.to_json();
+        assert!(json.starts_with(r#"{"data":[[".",".","@","@",".""#));
+
+        let grid_copy = Grid::from_json(&json).unwrap();
+
+        assert!(grid_copy == grid);
+        assert_eq!(grid_copy.n_rows(), 10);
+        assert_eq!(grid_copy.n_columns(), 10);
+        assert_eq!(grid_copy.mode(), GridMode::Toroidal);
+
+        grid.remove_item(0, 2);
+        grid.set(0, 0, GridType::Wall).unwrap();
+
+        let grid_copy = Grid::from_json(&grid.to_json()).unwrap();
+
+        assert!(grid_copy == grid);
+        assert_eq!(grid_copy.get(0, 2), Some(&GridType::Removed));
+        assert_eq!(grid_copy.get(0, 0), Some(&GridType::Wall));
+        assert_eq!(grid_copy.to_string().lines().next(), Some("#.x@.@@@@."));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_invalid_grids() {
+        let error = Grid::from_json(
+            r#"{"data":[["@","."],["@"]],"mode":"Bounded","removed_markers":false}"#,
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "Inconsistent row width (row 1 has 1, expected 2)"
+        );
+        assert!(
+            Grid::from_json(r#"{"data":[["@","?"]],"mode":"Bounded","removed_markers":false}"#)
+                .is_err()
+        );
+        assert!(
+            Grid::from_json(r#"{"data":[["@@"]],"mode":"Bounded","removed_markers":false}"#)
+                .is_err()
+        );
+
+        let grid = Grid::from_json(r#"{"data":[],"mode":"Bounded","removed_markers":false}"#);
+
+        assert!(grid.is_ok_and(|grid| grid.n_rows() == 0 && grid.n_columns() == 0));
+    }
+
+    #[test]
+    fn test_try_remove_item() {
+        let mut grid = Grid::from("@.\n#@").with_removed_markers(true);
+
+        assert_eq!(grid.try_remove_item(0, 0), Ok(RemovedKind::PaperRoll));
+        assert_eq!(grid.get(0, 0), Some(&GridType::Removed));
+
+        // Removing it again finds nothing, just like an empty cell
+        assert_eq!(grid.try_remove_item(0, 0), Ok(RemovedKind::AlreadyEmpty));
+        assert_eq!(grid.try_remove_item(0, 1), Ok(RemovedKind::AlreadyEmpty));
+
+        assert_eq!(
+            grid.try_remove_item(1, 0),
+            Err(RemoveError::NotRemovable { row: 1, col: 0 })
+        );
+        assert_eq!(
+            grid.try_remove_item(2, 1),
+            Err(RemoveError::OutOfBounds { row: 2, col: 1 })
+        );
+        assert_eq!(
+            grid.try_remove_item(1, 2).unwrap_err().to_string(),
+            "Position (1, 2) is out of the grid"
+        );
+        assert_eq!(grid.to_string(), "x.\n#@");
+
+        let mut grid = Grid::from("@@");
+
+        assert_eq!(grid.try_remove_item(0, 1), Ok(RemovedKind::PaperRoll));
+        assert_eq!(grid.try_remove_item(0, 1), Ok(RemovedKind::AlreadyEmpty));
+        assert_eq!(grid.to_string(), "@.");
+    }
+
+    #[test]
+    fn test_from_coords_round_trip() {
+        let grid = Grid::from(
+            r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#,
+        );
+
+        let rolls: Vec<(usize, usize)> = grid.roll_positions().collect();
+        assert_eq!(rolls.len(), 71);
+
+        assert_eq!(Grid::from_coords(10, 10, rolls.clone(), true), Ok(grid));
+
+        // Order doesn't matter, and duplicates are only rejected when asked to
+        let rolls = Vec::from([(1, 2), (0, 0), (1, 2)]);
+
+        assert_eq!(
+            Grid::from_coords(2, 3, rolls.clone(), false),
+            Ok(Grid::from("@..\n..@"))
+        );
+        assert_eq!(
+            Grid::from_coords(2, 3, rolls, true),
+            Err(GridBuildError::DuplicateRoll { row: 1, col: 2 })
+        );
+        assert_eq!(Grid::from_coords(0, 0, [], true), Ok(Grid::from("")));
+    }
+
+    #[test]
+    fn test_from_coords_out_of_bounds() {
+        assert_eq!(
+            Grid::from_coords(2, 3, [(0, 0), (2, 0)], false),
+            Err(GridBuildError::OutOfBounds { row: 2, col: 0 })
+        );
+
+        let error = Grid::from_coords(2, 3, [(1, 3)], false).unwrap_err();
+
+        assert_eq!(error, GridBuildError::OutOfBounds { row: 1, col: 3 });
+        assert_eq!(error.to_string(), "Paper roll at (1, 3) is out of the grid");
+    }
+
+    #[test]
+    fn test_generic_cells() {
+        let digit = |char: char| char.to_digit(10).map(|digit| digit as u8);
+        let grid: Grid<u8> = Grid::parse_with("123\n456\n789", digit).unwrap();
+
+        assert_eq!(grid.dimensions(), (3, 3));
+        assert_eq!(grid.get(1, 2), Some(&6));
+        assert_eq!(
+            grid.render_with(|&cell| (b'0' + cell) as char),
+            "123\n456\n789"
+        );
+
+        let neighbors_sum = |grid: &Grid<u8>, row, col, adjacency| {
+            grid.neighbors(row, col, adjacency)
+                .map(|(row, col)| *grid.get(row, col).unwrap() as usize)
+                .sum::<usize>()
+        };
+
+        assert_eq!(neighbors_sum(&grid, 1, 1, Adjacency::Eight), 40);
+        assert_eq!(neighbors_sum(&grid, 0, 0, Adjacency::Eight), 2 + 4 + 5);
+        assert_eq!(neighbors_sum(&grid, 0, 0, Adjacency::Four), 2 + 4);
+
+        let grid = grid.with_mode(GridMode::Toroidal);
+
+        assert_eq!(neighbors_sum(&grid, 0, 0, Adjacency::Four), 2 + 4 + 7 + 3);
+        assert_eq!(grid.get_signed(-1, -1), Some(&9));
+        assert_eq!(
+            grid.convolve_neighbor_count(5, Adjacency::Eight.offsets()),
+            Vec::from([
+                Vec::from([1, 1, 1]),
+                Vec::from([1, 0, 1]),
+                Vec::from([1, 1, 1])
+            ])
+        );
+
+        assert_eq!(
+            Grid::parse_with("12\n3", digit).err(),
+            Some(GridParseError::InconsistentRowWidth {
+                row: 1,
+                width: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(
+            Grid::parse_with("1a", digit).err(),
+            Some(GridParseError::InvalidCharacter {
+                char: 'a',
+                row: 0,
+                col: 1
+            })
+        );
+
+        // The warehouse alias is the default cell type
+        let warehouse_grid: WarehouseGrid =
+            Grid::parse_with("@.\n#@", GridType::from_char).unwrap();
+
+        assert_eq!(warehouse_grid, Grid::from("@.\n#@"));
+        assert_eq!(warehouse_grid.count_paper_rolls(), 2);
+    }
+
+    #[test]
+    fn test_grid_as_map_key() {
+        let mut seen_grids: HashMap<Grid, usize> = HashMap::new();
+
+        *seen_grids.entry(Grid::from("@@.\n.@@")).or_default() += 1;
+        *seen_grids
+            .entry(Grid::from("@@.\n.@@").with_mode(GridMode::Toroidal))
+            .or_default() += 1;
+
+        assert_eq!(seen_grids.len(), 1);
+        assert_eq!(seen_grids.get(&Grid::from("@@.\n.@@")), Some(&2));
+
+        // Same cells in a different shape, and a single different cell
+        *seen_grids.entry(Grid::from("@@..@@")).or_default() += 1;
+        *seen_grids.entry(Grid::from("@@.\n.@.")).or_default() += 1;
+
+        assert_eq!(seen_grids.len(), 3);
+        assert_eq!(seen_grids.get(&Grid::from("@@..@@")), Some(&1));
+        assert_eq!(seen_grids.get(&Grid::from("@@.\n.@.")), Some(&1));
+    }
+
+    #[test]
+    fn test_clone_and_debug() {
+        let grid = Grid::from("@@.\n.@#");
+        let mut cloned = grid.clone();
+
+        assert_eq!(cloned, grid);
+        assert_eq!(format!("{:?}", grid), "Grid 2x3 (Bounded)\n@@.\n.@#");
+
+        cloned.remove_item(0, 0);
+
+        assert_ne!(cloned, grid);
+        assert_eq!(grid.to_string(), "@@.\n.@#");
+        assert_eq!(format!("{:?}", cloned), "Grid 2x3 (Bounded)\n.@.\n.@#");
+    }
+
+    #[test]
+    fn test_rows() {
+        let grid = Grid::from("@.#\n..@");
+
+        assert_eq!(
+            grid.rows().collect::<Vec<&[GridType]>>(),
+            Vec::from([
+                &[GridType::PaperRoll, GridType::Empty, GridType::Wall][..],
+                &[GridType::Empty, GridType::Empty, GridType::PaperRoll][..]
+            ])
+        );
+        assert_eq!(grid.rows().next_back(), grid.rows().last());
+        assert_eq!(Grid::from("").rows().count(), 0);
+    }
+
+    #[test]
+    fn test_diagonal_iterators() {
+        let data = r#"
+            ..@@.@@@@.
+            @@@.@.@.@@
+            @@@@@.@.@@
+            @.@@@@..@.
+            @@.@@@@.@@
+            .@@@@@@@.@
+            .@.@.@.@@@
+            @.@@@.@@@@
+            .@@@@@@@@.
+            @.@.@@@.@."#;
+
+        let grid = Grid::from(data);
+
+        assert_eq!(grid.major_diagonal_iter(0).count(), 10);
+        assert_eq!(grid.major_diagonal_iter(1).count(), 9);
+        assert_eq!(grid.major_diagonal_iter(-1).count(), 9);
+        assert_eq!(grid.major_diagonal_iter(9).count(), 1);
+        assert_eq!(grid.major_diagonal_iter(10).count(), 0);
+
+        assert_eq!(grid.minor_diagonal_iter(0).count(), 10);
+        assert_eq!(grid.minor_diagonal_iter(1).count(), 9);
+        assert_eq!(grid.minor_diagonal_iter(-9).count(), 1);
+
+        let main_diagonal: Vec<&GridType> = grid.major_diagonal_iter(0).collect();
+        assert_eq!(main_diagonal[0], &GridType::Empty);
+        assert_eq!(main_diagonal[1], &GridType::PaperRoll);
+
+        let anti_diagonal: Vec<&GridType> = grid.minor_diagonal_iter(0).collect();
+        assert_eq!(anti_diagonal[0], &GridType::Empty);
+        assert_eq!(anti_diagonal[1], &GridType::PaperRoll);
+        assert_eq!(anti_diagonal[9], &GridType::PaperRoll);
+    }
+
+    #[test]
+    fn test_diagonal_iterators_non_square() {
+        let grid = Grid::from("@@@@\n....");
+
+        assert_eq!(grid.major_diagonal_iter(0).count(), 2);
+        assert_eq!(grid.major_diagonal_iter(2).count(), 2);
+        assert_eq!(grid.major_diagonal_iter(3).count(), 1);
+        assert_eq!(grid.major_diagonal_iter(-1).count(), 1);
+
+        assert_eq!(grid.minor_diagonal_iter(0).count(), 2);
+        assert_eq!(grid.minor_diagonal_iter(-3).count(), 1);
+    }
+}
@@ -0,0 +1,23 @@
+mod bitset;
+mod forklifts;
+#[cfg(any(test, feature = "gen"))]
+pub mod generator;
+mod grid;
+mod pos;
+mod sparse;
+mod store;
+
+pub use bitset::BitGrid;
+pub use forklifts::{
+    AccessibilityScan, CheckpointError, ForkLiftsHelper, PeelCheckpoint, PeelOutcome, PeelProgress,
+    RoundStats, render_round,
+};
+pub use grid::{
+    DimensionMismatch, Grid, GridBuildError, GridDiff, GridMode, GridParseError, GridSnapshot,
+    GridType, OutOfBoundsError, WarehouseGrid,
+};
+pub use pos::Pos;
+pub use sparse::SparseGrid;
+pub use store::{
+    Adjacency, BoundaryRule, GridStore, ParallelStore, RemoveError, RemovedKind, WallRule,
+};
@@ -1,171 +1,141 @@
-use std::fs::read_to_string;
+use std::{env, fs::File, io::BufReader, thread, time::Duration};
 
-#[derive(PartialEq, Debug)]
-enum GridType {
-    Empty,
-    PaperRoll,
-}
+use day_4::{AccessibilityScan, ForkLiftsHelper, Grid, GridStore, PeelOutcome, render_round};
 
-impl From<&char> for GridType {
-    fn from(value: &char) -> Self {
-        match value {
-            '.' => Self::Empty,
-            '@' => Self::PaperRoll,
-            _ => panic!("Invalid char received"),
-        }
-    }
+const DEFAULT_DELAY_MS: u64 = 500;
+const DEFAULT_CHECKPOINT_PATH: &str = "day_4/data/checkpoint.txt";
+
+fn read_grid() -> Grid {
+    let file = File::open("day_4/data/input.txt").expect("File not found or unabled to read");
+
+    Grid::from_reader(BufReader::new(file))
+        .unwrap_or_else(|error| panic!("Invalid grid: {}", error))
 }
 
-struct Grid {
-    n_rows: usize,
-    n_columns: usize,
-    data: Vec<Vec<GridType>>,
+/// Value following the given flag, if the flag is present
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<Option<&'a str>> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .map(|index| args.get(index + 1).map(|value| value.as_str()))
 }
 
-impl Grid {
-    fn is_within_bounds(&self, row: isize, col: isize) -> bool {
-        row >= 0 && row < self.n_rows as isize && col >= 0 && col < self.n_columns as isize
-    }
+/// Accessible paper rolls per row and per column, right-aligned by index
+fn print_accessibility_stats(grid: &Grid) {
+    let (per_row, per_column) = AccessibilityScan::new(grid).accessible_per_row_and_column();
 
-    fn is_paper_roll(&self, row: usize, col: usize) -> bool {
-        self.data[row][col] == GridType::PaperRoll
-    }
+    for (label, counts) in [("Row", per_row), ("Column", per_column)] {
+        let width = counts.len().to_string().len().max(label.len());
 
-    fn remove_item(&mut self, row: usize, col: usize) {
-        self.data[row][col] = GridType::Empty;
-    }
-}
+        println!("{:>width$} | Accessible", label);
 
-impl From<&str> for Grid {
-    fn from(value: &str) -> Self {
-        let grid_data: Vec<Vec<GridType>> = value
-            .trim()
-            .lines()
-            .map(|line| {
-                line.trim()
-                    .chars()
-                    .map(|item| GridType::from(&item))
-                    .collect()
-            })
-            .collect();
-
-        Self {
-            n_rows: grid_data.len(),
-            n_columns: grid_data.first().unwrap_or(&Vec::new()).len(),
-            data: grid_data,
+        for (index, count) in counts.iter().enumerate() {
+            println!("{:>width$} | {}", index, count);
         }
-    }
-}
 
-struct ForkLiftsHelper<'a> {
-    grid: &'a mut Grid,
+        println!();
+    }
 }
 
-impl<'a> ForkLiftsHelper<'a> {
-    const ADJACENT_OFFSETS: [[isize; 2]; 8] = [
-        [1, 0],
-        [0, 1],
-        [1, 1],
-        [-1, 0],
-        [0, -1],
-        [-1, -1],
-        [-1, 1],
-        [1, -1],
-    ];
-
-    fn new(grid: &'a mut Grid) -> Self {
-        Self { grid }
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let is_animate = args.iter().any(|arg| arg == "--animate");
+    let delay_ms = arg_value(&args, "--delay-ms").map_or(DEFAULT_DELAY_MS, |value| {
+        value
+            .and_then(|value| value.parse().ok())
+            .expect("Invalid --delay-ms value")
+    });
+
+    let is_stats = args.iter().any(|arg| arg == "--stats");
+    let is_resume = args.iter().any(|arg| arg == "--resume");
+    let stop_after = arg_value(&args, "--stop-after").map(|value| {
+        value
+            .and_then(|value| value.parse::<usize>().ok())
+            .expect("Invalid --stop-after value")
+    });
+    let checkpoint_path = arg_value(&args, "--checkpoint")
+        .map_or(DEFAULT_CHECKPOINT_PATH, |value| {
+            value.expect("Missing --checkpoint path")
+        });
+
+    if is_resume {
+        let (mut grid, checkpoint) = ForkLiftsHelper::load_checkpoint(checkpoint_path)
+            .unwrap_or_else(|error| panic!("Unable to resume: {}", error));
+
+        let mut forklifts_helper = ForkLiftsHelper::from_checkpoint(&mut grid, checkpoint);
+        forklifts_helper.run_queue_based();
+
+        println!(
+            "Day4 -> Part 2: {} (resumed after {} rounds, {} rounds in total)",
+            forklifts_helper.progress().removed,
+            checkpoint.progress.rounds,
+            forklifts_helper.progress().rounds
+        );
+
+        return;
     }
-    fn is_item_accessible(&self, row: usize, col: usize) -> bool {
-        if !self.grid.is_paper_roll(row, col) {
-            return false;
-        }
 
-        Self::ADJACENT_OFFSETS
-            .iter()
-            .map(|[row_offset, col_offset]| [row as isize + row_offset, col as isize + col_offset])
-            .filter(|&[row, col]| {
-                self.grid.is_within_bounds(row, col)
-                    && self.grid.is_paper_roll(row as usize, col as usize)
-            })
-            .count()
-            < 4
-    }
+    let mut grid = read_grid();
+
+    println!(
+        "Day4 -> Part 1: {}",
+        AccessibilityScan::new(&grid)
+            .find_accessible_paper_rolls()
+            .len()
+    );
 
-    fn find_accessible_paper_rolls(&self) -> impl Iterator<Item = (usize, usize)> {
-        (0..self.grid.n_rows)
-            .flat_map(|row_index| {
-                (0..self.grid.n_columns)
-                    .map(|col_index| (row_index, col_index))
-                    .collect::<Vec<(usize, usize)>>()
-            })
-            .filter(|&(row, col)| self.is_item_accessible(row, col))
+    if is_stats {
+        print_accessibility_stats(&grid);
     }
 
-    fn iterative_remove_accessible_paper_rolls(&mut self) -> usize {
-        let mut removed_paper_rolls = 0;
+    if let Some(n_rounds) = stop_after {
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+        forklifts_helper.run_rounds(n_rounds);
 
-        loop {
-            let valid_paper_rolls: Vec<(usize, usize)> =
-                self.find_accessible_paper_rolls().collect();
+        forklifts_helper
+            .save_checkpoint(checkpoint_path)
+            .unwrap_or_else(|error| panic!("Unable to save the checkpoint: {}", error));
 
-            if valid_paper_rolls.is_empty() {
-                return removed_paper_rolls;
-            }
+        let progress = forklifts_helper.progress();
 
-            for &(row, col) in valid_paper_rolls.iter() {
-                self.grid.remove_item(row, col);
-            }
+        println!(
+            "Checkpoint saved to {} after {} rounds, {} removed",
+            checkpoint_path, progress.rounds, progress.removed
+        );
 
-            removed_paper_rolls += valid_paper_rolls.len();
-        }
+        return;
     }
-}
 
-fn main() {
-    let data = read_to_string("day_4/data/input.txt").expect("File not found or unabled to read");
-
-    let mut grid = Grid::from(data.as_str());
-    let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
-
-    println!(
-        "Day4 -> Part 1: {}",
-        forklifts_helper.find_accessible_paper_rolls().count()
-    );
+    let history = ForkLiftsHelper::new(&mut grid).run_queue_based();
 
     println!(
         "Day4 -> Part 2: {}",
-        forklifts_helper.iterative_remove_accessible_paper_rolls()
+        history.iter().map(|round| round.len()).sum::<usize>()
     );
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_example_input() {
-        let data = r#"
-            ..@@.@@@@.
-            @@@.@.@.@@
-            @@@@@.@.@@
-            @.@@@@..@.
-            @@.@@@@.@@
-            .@@@@@@@.@
-            .@.@.@.@@@
-            @.@@@.@@@@
-            .@@@@@@@@.
-            @.@.@@@.@."#;
-
-        let mut grid = Grid::from(data);
-        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+    if is_stats {
+        let peel_outcome = PeelOutcome::from_history(&history, grid.count_paper_rolls());
 
-        let valid_paper_rolls = forklifts_helper.find_accessible_paper_rolls();
+        // Same 0-based round index as `PeelOutcome::peak_round`
+        if let Some((round_index, removed)) = peel_outcome.peak_round() {
+            println!("Peak round index {} removed {} rolls", round_index, removed);
+        }
+    }
+
+    if is_animate {
+        // Replay the removals on a fresh grid, showing each round before applying it
+        let mut grid = read_grid();
 
-        assert_eq!(valid_paper_rolls.count(), 13);
+        for (round_index, round) in history.iter().enumerate() {
+            println!();
+            println!("Round {}: {} removed", round_index + 1, round.len());
+            println!("{}", render_round(&grid, round));
 
-        let removed_paper_rolls = forklifts_helper.iterative_remove_accessible_paper_rolls();
+            for pos in round.iter() {
+                grid.remove_item(pos.row, pos.col);
+            }
 
-        assert_eq!(removed_paper_rolls, 43);
+            thread::sleep(Duration::from_millis(delay_ms));
+        }
     }
 }
@@ -0,0 +1,118 @@
+use std::fmt;
+
+use crate::GridStore;
+
+/// Cell of a grid. Ordered by row and then column, so sorting keeps the row-major order
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "(usize, usize)", into = "(usize, usize)")
+)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Position moved by the given offsets, following the grid mode: `None` when it falls out
+    /// of a bounded grid, wrapped around the edges of a toroidal one
+    pub fn offset(
+        &self,
+        row_offset: isize,
+        col_offset: isize,
+        grid: &(impl GridStore + ?Sized),
+    ) -> Option<Pos> {
+        grid.mode()
+            .wrap_position(
+                (grid.n_rows(), grid.n_columns()),
+                self.row as isize + row_offset,
+                self.col as isize + col_offset,
+            )
+            .map(Pos::from)
+    }
+}
+
+impl From<(usize, usize)> for Pos {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<Pos> for (usize, usize) {
+    fn from(pos: Pos) -> Self {
+        (pos.row, pos.col)
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.row, self.col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, GridMode};
+
+    #[test]
+    fn test_offset_at_grid_edges() {
+        let grid = Grid::from("@@@@\n@@@@\n@@@@");
+
+        // Top and left edges
+        let top_left = Pos::new(0, 0);
+        assert_eq!(top_left.offset(-1, 0, &grid), None);
+        assert_eq!(top_left.offset(0, -1, &grid), None);
+        assert_eq!(top_left.offset(1, 1, &grid), Some(Pos::new(1, 1)));
+
+        // Bottom and right edges
+        let bottom_right = Pos::new(2, 3);
+        assert_eq!(bottom_right.offset(1, 0, &grid), None);
+        assert_eq!(bottom_right.offset(0, 1, &grid), None);
+        assert_eq!(bottom_right.offset(-1, -1, &grid), Some(Pos::new(1, 2)));
+
+        assert_eq!(Pos::new(0, 3).offset(-1, 1, &grid), None);
+        assert_eq!(Pos::new(2, 0).offset(1, -1, &grid), None);
+        assert_eq!(Pos::new(1, 2).offset(0, 0, &grid), Some(Pos::new(1, 2)));
+
+        let grid = grid.with_mode(GridMode::Toroidal);
+
+        assert_eq!(top_left.offset(-1, -1, &grid), Some(bottom_right));
+        assert_eq!(bottom_right.offset(1, 1, &grid), Some(top_left));
+    }
+
+    #[test]
+    fn test_equality_and_ordering() {
+        assert_eq!(Pos::new(1, 2), Pos::from((1, 2)));
+        assert_ne!(Pos::new(1, 2), Pos::new(2, 1));
+        assert_eq!(<(usize, usize)>::from(Pos::new(1, 2)), (1, 2));
+
+        // Row first, so a later row goes after any column of an earlier one
+        assert!(Pos::new(0, 9) < Pos::new(1, 0));
+        assert!(Pos::new(1, 0) < Pos::new(1, 1));
+
+        let mut positions = Vec::from([
+            Pos::new(2, 0),
+            Pos::new(0, 5),
+            Pos::new(1, 3),
+            Pos::new(0, 1),
+        ]);
+        positions.sort();
+
+        assert_eq!(
+            positions,
+            Vec::from([
+                Pos::new(0, 1),
+                Pos::new(0, 5),
+                Pos::new(1, 3),
+                Pos::new(2, 0)
+            ])
+        );
+        assert_eq!(Pos::new(3, 14).to_string(), "(3,14)");
+    }
+}
@@ -0,0 +1,272 @@
+use std::{collections::BTreeSet, ops::Range, str::FromStr};
+
+use crate::{Grid, GridMode, GridParseError, GridStore, GridType, grid::parse_rows};
+
+/// Grid keeping only the paper roll and wall positions, for huge and mostly empty warehouses
+pub struct SparseGrid {
+    n_rows: usize,
+    n_columns: usize,
+    paper_rolls: BTreeSet<(usize, usize)>,
+    walls: BTreeSet<(usize, usize)>,
+    mode: GridMode,
+}
+
+impl SparseGrid {
+    pub fn new(
+        n_rows: usize,
+        n_columns: usize,
+        paper_rolls: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        let paper_rolls: BTreeSet<(usize, usize)> = paper_rolls.into_iter().collect();
+
+        assert!(
+            paper_rolls
+                .iter()
+                .all(|&(row, col)| row < n_rows && col < n_columns),
+            "Paper roll outside of the grid dimensions"
+        );
+
+        Self {
+            n_rows,
+            n_columns,
+            paper_rolls,
+            walls: BTreeSet::new(),
+            mode: GridMode::Bounded,
+        }
+    }
+
+    pub fn with_walls(mut self, walls: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.walls.extend(walls);
+
+        assert!(
+            self.walls
+                .iter()
+                .all(|&(row, col)| row < self.n_rows && col < self.n_columns),
+            "Wall outside of the grid dimensions"
+        );
+
+        self
+    }
+
+    pub fn with_mode(mut self, mode: GridMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+impl GridStore for SparseGrid {
+    fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    fn n_columns(&self) -> usize {
+        self.n_columns
+    }
+
+    fn mode(&self) -> GridMode {
+        self.mode
+    }
+
+    fn is_paper_roll(&self, row: usize, col: usize) -> bool {
+        self.paper_rolls.contains(&(row, col))
+    }
+
+    fn is_occupied(&self, row: usize, col: usize) -> bool {
+        self.is_paper_roll(row, col) || self.walls.contains(&(row, col))
+    }
+
+    fn remove_item(&mut self, row: usize, col: usize) -> GridType {
+        if self.paper_rolls.remove(&(row, col)) {
+            return GridType::PaperRoll;
+        }
+
+        match self.walls.contains(&(row, col)) {
+            true => GridType::Wall,
+            false => GridType::Empty,
+        }
+    }
+
+    fn paper_rolls_in_rows(&self, rows: Range<usize>) -> impl Iterator<Item = (usize, usize)> {
+        self.paper_rolls
+            .range((rows.start, 0)..(rows.end, 0))
+            .copied()
+    }
+
+    fn paper_rolls(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.paper_rolls.iter().copied()
+    }
+
+    fn count_paper_rolls(&self) -> usize {
+        self.paper_rolls.len()
+    }
+}
+
+impl From<&Grid> for SparseGrid {
+    fn from(grid: &Grid) -> Self {
+        Self::new(grid.n_rows(), grid.n_columns(), grid.paper_rolls())
+            .with_walls(grid.cells_of_type(GridType::Wall))
+            .with_mode(grid.mode())
+    }
+}
+
+impl FromStr for SparseGrid {
+    type Err = GridParseError;
+
+    /// Same format as the dense grid, but only one row is kept in memory at a time
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut sparse_grid = Self::new(0, 0, []);
+
+        for (row, row_data) in parse_rows(value).enumerate() {
+            let row_data = row_data?;
+
+            sparse_grid.n_rows = row + 1;
+            sparse_grid.n_columns = row_data.len();
+
+            for (col, cell) in row_data.iter().enumerate() {
+                match cell {
+                    GridType::PaperRoll => sparse_grid.paper_rolls.insert((row, col)),
+                    GridType::Wall => sparse_grid.walls.insert((row, col)),
+                    GridType::Empty | GridType::Removed => false,
+                };
+            }
+        }
+
+        Ok(sparse_grid)
+    }
+}
+
+impl From<&str> for SparseGrid {
+    fn from(value: &str) -> Self {
+        value
+            .parse()
+            .unwrap_or_else(|error| panic!("Invalid grid: {}", error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccessibilityScan, ForkLiftsHelper, Pos};
+
+    const EXAMPLE_DATA: &str = r#"
+        ..@@.@@@@.
+        @@@.@.@.@@
+        @@@@@.@.@@
+        @.@@@@..@.
+        @@.@@@@.@@
+        .@@@@@@@.@
+        .@.@.@.@@@
+        @.@@@.@@@@
+        .@@@@@@@@.
+        @.@.@@@.@."#;
+
+    #[test]
+    fn test_same_answers_as_dense_grid() {
+        let mut grid = Grid::from(EXAMPLE_DATA);
+        let mut sparse_grid = SparseGrid::from(EXAMPLE_DATA);
+
+        assert_eq!(sparse_grid.n_rows(), 10);
+        assert_eq!(sparse_grid.n_columns(), 10);
+        assert_eq!(sparse_grid.count_paper_rolls(), grid.count_paper_rolls());
+
+        let mut forklifts_helper = ForkLiftsHelper::new(&mut grid);
+        let mut sparse_forklifts_helper = ForkLiftsHelper::new(&mut sparse_grid);
+
+        let accessible_paper_rolls: Vec<Pos> = forklifts_helper
+            .scan()
+            .find_accessible_paper_rolls()
+            .collect();
+
+        assert_eq!(accessible_paper_rolls.len(), 13);
+        assert_eq!(
+            sparse_forklifts_helper
+                .scan()
+                .find_accessible_paper_rolls()
+                .collect::<Vec<Pos>>(),
+            accessible_paper_rolls
+        );
+
+        assert_eq!(
+            sparse_forklifts_helper.run_with_history(),
+            forklifts_helper.run_with_history()
+        );
+        assert_eq!(sparse_grid.count_paper_rolls(), 28);
+    }
+
+    #[test]
+    fn test_same_answers_as_dense_grid_toroidal() {
+        let mut grid = Grid::from(EXAMPLE_DATA).with_mode(GridMode::Toroidal);
+        let mut sparse_grid = SparseGrid::from(&grid);
+
+        assert_eq!(
+            ForkLiftsHelper::new(&mut sparse_grid).run_to_completion(),
+            ForkLiftsHelper::new(&mut grid).run_to_completion()
+        );
+    }
+
+    #[test]
+    fn test_same_answers_as_dense_grid_with_walls() {
+        let data = "@@@@@\n@###@\n@@@@@";
+
+        let mut grid = Grid::from(data);
+        let mut sparse_grid = SparseGrid::from(data);
+
+        assert_eq!(
+            ForkLiftsHelper::new(&mut sparse_grid).run_with_history(),
+            ForkLiftsHelper::new(&mut grid).run_with_history()
+        );
+        assert_eq!(sparse_grid.remove_item(1, 1), GridType::Wall);
+        assert_eq!(sparse_grid.remove_item(0, 0), GridType::Empty);
+        assert!(sparse_grid.is_occupied(1, 1));
+    }
+
+    #[test]
+    fn test_checked_accessibility_matches_sparse_grid() {
+        for mode in [GridMode::Bounded, GridMode::Toroidal] {
+            let grid = Grid::from(EXAMPLE_DATA).with_mode(mode);
+            let sparse_grid = SparseGrid::from(&grid);
+
+            assert_eq!(
+                AccessibilityScan::new(&grid)
+                    .accessible_paper_rolls()
+                    .collect::<Vec<Pos>>(),
+                AccessibilityScan::new(&sparse_grid)
+                    .accessible_paper_rolls()
+                    .collect::<Vec<Pos>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_huge_sparse_grid() {
+        let mut sparse_grid = SparseGrid::new(
+            1_000_000,
+            1_000_000,
+            [(0, 0), (0, 1), (1, 0), (1, 1), (500_000, 500_000)],
+        );
+
+        let peel_outcome = ForkLiftsHelper::new(&mut sparse_grid).run_to_completion();
+
+        assert_eq!(peel_outcome.removed, 5);
+        assert_eq!(peel_outcome.rounds, 1);
+        assert_eq!(peel_outcome.remaining, 0);
+    }
+
+    #[test]
+    fn test_parse_sparse_grid_errors() {
+        assert_eq!(
+            "..@\n@@\n...".parse::<SparseGrid>().err(),
+            Some(GridParseError::InconsistentRowWidth {
+                row: 1,
+                width: 2,
+                expected: 3
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Paper roll outside of the grid dimensions")]
+    fn test_roll_out_of_bounds_panic() {
+        let _ = SparseGrid::new(2, 2, [(2, 0)]);
+    }
+}
@@ -0,0 +1,272 @@
+use std::{error::Error, fmt, ops::Range};
+
+use crate::{GridMode, GridType};
+
+/// Neighborhood of a cell when checking accessibility
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Adjacency {
+    /// Orthogonal and diagonal neighbors
+    Eight,
+    /// Orthogonal neighbors only, up, down, left and right
+    Four,
+}
+
+impl Adjacency {
+    const EIGHT_OFFSETS: [[isize; 2]; 8] = [
+        [1, 0],
+        [0, 1],
+        [-1, 0],
+        [0, -1],
+        [1, 1],
+        [-1, -1],
+        [-1, 1],
+        [1, -1],
+    ];
+
+    pub fn offsets(&self) -> &'static [[isize; 2]] {
+        match self {
+            Self::Eight => &Self::EIGHT_OFFSETS,
+            // The orthogonal offsets go first in the eight neighbors
+            Self::Four => &Self::EIGHT_OFFSETS[..4],
+        }
+    }
+
+    /// Positions around the given one in a grid of the given dimensions and mode, shared by
+    /// every grid backend so they all wrap the same way. Each neighbor is yielded once and never
+    /// the position itself, even when several offsets wrap onto the same cell of a toroidal grid
+    /// narrower than 3 cells
+    pub(crate) fn neighbor_positions(
+        self,
+        mode: GridMode,
+        dimensions: (usize, usize),
+        row: usize,
+        col: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (n_rows, n_columns) = dimensions;
+        let may_repeat = mode == GridMode::Toroidal && (n_rows < 3 || n_columns < 3);
+
+        let offsets = self.offsets();
+        let wrap = move |&[row_offset, col_offset]: &[isize; 2]| {
+            mode.wrap_position(
+                dimensions,
+                row as isize + row_offset,
+                col as isize + col_offset,
+            )
+        };
+
+        offsets
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, offset)| {
+                let position = wrap(offset)?;
+
+                // Only checked when it can happen, keeping the common case cheap
+                let is_repeated = may_repeat
+                    && (position == (row, col)
+                        || offsets[..index]
+                            .iter()
+                            .any(|previous| wrap(previous) == Some(position)));
+
+                (!is_repeated).then_some(position)
+            })
+    }
+}
+
+/// How the cells beyond the edges of a bounded grid count as neighbors. Toroidal grids
+/// have no such cells
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum BoundaryRule {
+    #[default]
+    OutsideIsEmpty,
+    /// The warehouse walls press against the border, taking the neighbor slots outside
+    OutsideIsOccupied,
+}
+
+/// Whether walls take a neighbor slot when checking accessibility
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum WallRule {
+    #[default]
+    WallIsOccupied,
+    /// Only paper rolls count toward the threshold, walls are as good as empty cells
+    WallIsEmpty,
+}
+
+/// What a successful removal found in the cell
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum RemovedKind {
+    PaperRoll,
+    /// Nothing to remove, the cell was empty or its roll was already removed
+    AlreadyEmpty,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum RemoveError {
+    OutOfBounds {
+        row: usize,
+        col: usize,
+    },
+    /// Walls take a neighbor slot but are never removed
+    NotRemovable {
+        row: usize,
+        col: usize,
+    },
+}
+
+impl fmt::Display for RemoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { row, col } => {
+                write!(f, "Position ({}, {}) is out of the grid", row, col)
+            }
+            Self::NotRemovable { row, col } => {
+                write!(f, "Item at ({}, {}) can't be removed", row, col)
+            }
+        }
+    }
+}
+
+impl Error for RemoveError {}
+
+/// Storage backend for a warehouse, as seen by the forklifts
+pub trait GridStore {
+    fn n_rows(&self) -> usize;
+
+    fn n_columns(&self) -> usize;
+
+    fn mode(&self) -> GridMode;
+
+    fn is_paper_roll(&self, row: usize, col: usize) -> bool;
+
+    /// Cells taking a neighbor slot when checking accessibility
+    fn is_occupied(&self, row: usize, col: usize) -> bool {
+        self.is_paper_roll(row, col)
+    }
+
+    /// Only paper rolls can be removed, any other cell is left unchanged. Returns what the cell
+    /// held before, `GridType::Empty` outside of the grid
+    fn remove_item(&mut self, row: usize, col: usize) -> GridType;
+
+    /// Checked `remove_item`, telling apart a removed roll from a cell that was already empty
+    fn try_remove_item(&mut self, row: usize, col: usize) -> Result<RemovedKind, RemoveError> {
+        if row >= self.n_rows() || col >= self.n_columns() {
+            return Err(RemoveError::OutOfBounds { row, col });
+        }
+
+        match self.remove_item(row, col) {
+            GridType::PaperRoll => Ok(RemovedKind::PaperRoll),
+            GridType::Wall => Err(RemoveError::NotRemovable { row, col }),
+            GridType::Empty | GridType::Removed => Ok(RemovedKind::AlreadyEmpty),
+        }
+    }
+
+    /// Positions of the paper rolls within the given rows, in row-major order
+    fn paper_rolls_in_rows(&self, rows: Range<usize>) -> impl Iterator<Item = (usize, usize)>;
+
+    /// Positions of every paper roll, in row-major order
+    fn paper_rolls(&self) -> impl Iterator<Item = (usize, usize)> {
+        self.paper_rolls_in_rows(0..self.n_rows())
+    }
+
+    fn count_paper_rolls(&self) -> usize {
+        self.paper_rolls().count()
+    }
+
+    /// Cell at the signed coordinates, wrapped around in toroidal mode and `None` out of a
+    /// bounded grid. Backends not keeping the cells rebuild them from the occupancy checks
+    fn get_signed(&self, row: isize, col: isize) -> Option<GridType> {
+        let (row, col) = self
+            .mode()
+            .wrap_position((self.n_rows(), self.n_columns()), row, col)?;
+
+        Some(
+            match (self.is_paper_roll(row, col), self.is_occupied(row, col)) {
+                (true, _) => GridType::PaperRoll,
+                (false, true) => GridType::Wall,
+                (false, false) => GridType::Empty,
+            },
+        )
+    }
+
+    fn is_within_bounds(&self, row: isize, col: isize) -> bool {
+        self.mode()
+            .wrap_position((self.n_rows(), self.n_columns()), row, col)
+            .is_some()
+    }
+
+    /// Wrap any position back into the grid dimensions, whatever the grid mode
+    fn is_within_bounds_wrap(&self, row: isize, col: isize) -> (usize, usize) {
+        GridMode::Toroidal
+            .wrap_position((self.n_rows(), self.n_columns()), row, col)
+            .expect("Can't wrap positions around an empty grid")
+    }
+
+    /// Positions around the given one that exist in the current mode
+    fn neighbors(
+        &self,
+        row: usize,
+        col: usize,
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        adjacency.neighbor_positions(self.mode(), (self.n_rows(), self.n_columns()), row, col)
+    }
+
+    fn occupied_neighbor_count(&self, row: usize, col: usize, adjacency: Adjacency) -> usize {
+        self.neighbors(row, col, adjacency)
+            .filter(|&(row, col)| self.is_occupied(row, col))
+            .count()
+    }
+
+    /// Paper rolls around the given position, leaving out walls, stopping once `limit` of them
+    /// are found
+    fn count_adjacent_paper_rolls_up_to(
+        &self,
+        row: usize,
+        col: usize,
+        adjacency: Adjacency,
+        limit: usize,
+    ) -> usize {
+        self.neighbors(row, col, adjacency)
+            .filter(|&(row, col)| self.is_paper_roll(row, col))
+            .take(limit)
+            .count()
+    }
+
+    /// Occupied cells around the given position other than paper rolls, which are never removed
+    fn count_adjacent_walls(&self, row: usize, col: usize, adjacency: Adjacency) -> usize {
+        self.neighbors(row, col, adjacency)
+            .filter(|&(row, col)| self.is_occupied(row, col) && !self.is_paper_roll(row, col))
+            .count()
+    }
+
+    /// Same as `occupied_neighbor_count`, but stopping as soon as `limit` occupied neighbors
+    /// are found, which is enough to compare them against a threshold
+    fn count_adjacent_occupied_up_to(
+        &self,
+        row: usize,
+        col: usize,
+        adjacency: Adjacency,
+        limit: usize,
+    ) -> usize {
+        // `take` stops pulling neighbors once it has yielded `limit` of them
+        self.neighbors(row, col, adjacency)
+            .filter(|&(row, col)| self.is_occupied(row, col))
+            .take(limit)
+            .count()
+    }
+}
+
+/// Store the scans may share between threads: any `Sync` one with the `parallel` feature,
+/// any store at all without it
+#[cfg(feature = "parallel")]
+pub trait ParallelStore: GridStore + Sync {}
+
+#[cfg(feature = "parallel")]
+impl<S: GridStore + Sync> ParallelStore for S {}
+
+/// Store the scans may share between threads: any `Sync` one with the `parallel` feature,
+/// any store at all without it
+#[cfg(not(feature = "parallel"))]
+pub trait ParallelStore: GridStore {}
+
+#[cfg(not(feature = "parallel"))]
+impl<S: GridStore> ParallelStore for S {}
@@ -0,0 +1,71 @@
+//! Counts the allocations of the current thread, kept in its own test binary so the global
+//! allocator doesn't affect any other test.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use day_4::{AccessibilityScan, Adjacency, Grid, GridStore, generator::random_grid};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn lazy_accessible_scan_does_not_allocate() {
+    let grid = Grid::from(random_grid(300, 300, 65, 7).as_str());
+    let accessibility_scan = AccessibilityScan::new(&grid);
+
+    let (accessible_count, allocations) =
+        allocations_during(|| accessibility_scan.accessible_paper_rolls().count());
+
+    assert!(accessible_count > 0);
+    assert_eq!(allocations, 0);
+
+    // Collecting allocates the result, but nothing per row
+    let (accessible_paper_rolls, allocations) =
+        allocations_during(|| accessibility_scan.find_accessible_paper_rolls());
+
+    assert_eq!(accessible_paper_rolls.len(), accessible_count);
+    assert!(allocations < 300);
+}
+
+#[test]
+fn neighbors_do_not_allocate() {
+    let grid = Grid::from(random_grid(50, 50, 65, 7).as_str());
+
+    let (occupied_neighbors, allocations) = allocations_during(|| {
+        (0..grid.n_rows())
+            .flat_map(|row| (0..grid.n_columns()).map(move |col| (row, col)))
+            .map(|(row, col)| grid.occupied_neighbor_count(row, col, Adjacency::Eight))
+            .sum::<usize>()
+    });
+
+    assert!(occupied_neighbors > 0);
+    assert_eq!(allocations, 0);
+}
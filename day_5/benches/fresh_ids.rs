@@ -0,0 +1,51 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use day_5::KitchenDB;
+
+/// Kitchen database with `n_ranges` random ranges and `n_ingredients` random IDs
+fn random_kitchen_db(n_ranges: usize, n_ingredients: usize, seed: u64) -> KitchenDB {
+    let mut state = seed;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % 1_000_000_000
+    };
+
+    let ranges = (0..n_ranges)
+        .map(|_| {
+            let lower_id = next();
+            format!("{}-{}", lower_id, lower_id + next() % 100_000)
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let ingredients = (0..n_ingredients)
+        .map(|_| next().to_string())
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    KitchenDB::from(format!("{}\n\n{}", ranges, ingredients).as_str())
+}
+
+fn fresh_available_ids(c: &mut Criterion) {
+    let mut kitchen_db = random_kitchen_db(10_000, 10_000, 42);
+    kitchen_db.build_index();
+
+    let mut group = c.benchmark_group("fresh_available_ids");
+    group.sample_size(10);
+
+    group.bench_function("linear", |b| {
+        b.iter(|| black_box(kitchen_db.get_fresh_available_ids().count()))
+    });
+
+    group.bench_function("segment_tree", |b| {
+        b.iter(|| black_box(kitchen_db.get_fresh_available_ids_indexed().count()))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, fresh_available_ids);
+criterion_main!(benches);
@@ -0,0 +1,1226 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    error::Error,
+    fmt,
+    ops::RangeInclusive,
+};
+
+pub use ranges::{Id, InclusiveRange};
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum ParseError {
+    MissingHeader,
+    MissingColumn { line_number: usize },
+    UnknownType { line_number: usize, value: String },
+    InvalidNumber { line_number: usize, value: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "Missing \"type,value\" header row"),
+            Self::MissingColumn { line_number } => {
+                write!(f, "Line {}: Missing value column", line_number)
+            }
+            Self::UnknownType { line_number, value } => {
+                write!(f, "Line {}: Unknown type {:?}", line_number, value)
+            }
+            Self::InvalidNumber { line_number, value } => {
+                write!(f, "Line {}: Invalid number in {:?}", line_number, value)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FreshRange {
+    lower_id: Id,
+    upper_id: Id,
+}
+
+impl FreshRange {
+    fn is_id_fresh(&self, id: &Id) -> bool {
+        self.contains(*id)
+    }
+
+    pub fn get_fresh_ids(&self) -> FreshIds {
+        FreshIds {
+            ids: self.lower_id..=self.upper_id,
+        }
+    }
+
+    pub fn overlap_with(&self, other: &FreshRange) -> bool {
+        self.lower_id <= other.upper_id && other.lower_id <= self.upper_id
+    }
+
+    /// Central ID, truncated toward the lower one for even-sized ranges. `None` for an
+    /// inverted range, which has no IDs
+    pub fn midpoint(&self) -> Option<Id> {
+        // Halving the distance first can't overflow, unlike adding both bounds
+        let distance = self.upper_id.checked_sub(self.lower_id)?;
+
+        Some(self.lower_id + distance / 2)
+    }
+
+    /// Never true for an inverted range, which has no midpoint
+    pub fn contains_midpoint_of(&self, other: &FreshRange) -> bool {
+        other.midpoint().is_some_and(|id| self.is_id_fresh(&id))
+    }
+
+    /// Range moved by `delta` IDs, `None` if either bound would leave the ID space
+    pub fn checked_shift(&self, delta: i64) -> Option<FreshRange> {
+        Some(FreshRange {
+            lower_id: self.lower_id.checked_add_signed(delta)?,
+            upper_id: self.upper_id.checked_add_signed(delta)?,
+        })
+    }
+
+    /// Range moved by `delta` IDs, clamping each bound to the ID space
+    pub fn saturating_shift(&self, delta: i64) -> FreshRange {
+        FreshRange {
+            lower_id: self.lower_id.saturating_add_signed(delta),
+            upper_id: self.upper_id.saturating_add_signed(delta),
+        }
+    }
+}
+
+impl InclusiveRange for FreshRange {
+    fn lower(&self) -> Id {
+        self.lower_id
+    }
+
+    fn upper(&self) -> Id {
+        self.upper_id
+    }
+}
+
+impl From<&str> for FreshRange {
+    fn from(value: &str) -> Self {
+        let mut ids = value.split('-').take(2);
+
+        let lower_id = ids
+            .next()
+            .unwrap()
+            .parse()
+            .expect("Invalid first number in input");
+
+        let upper_id = ids
+            .next()
+            .unwrap()
+            .parse()
+            .expect("Invalid second number in input");
+
+        Self { lower_id, upper_id }
+    }
+}
+
+/// IDs of a fresh range. Like `RangeInclusive<u64>` it isn't an `ExactSizeIterator`, as its
+/// length may not fit in a `usize`, so `len` counts the remaining IDs in a `u128` instead
+pub struct FreshIds {
+    ids: RangeInclusive<Id>,
+}
+
+impl FreshIds {
+    /// Remaining IDs, without consuming them
+    pub fn len(&self) -> u128 {
+        match self.ids.is_empty() {
+            true => 0,
+            false => (*self.ids.end() - *self.ids.start()) as u128 + 1,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+impl Iterator for FreshIds {
+    type Item = Id;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ids.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ids.size_hint()
+    }
+}
+
+/// Fresh range over a circular ID space, where IDs are compared modulo `modulus`
+pub struct PeriodicFreshRange {
+    lower_id: Id,
+    upper_id: Id,
+    modulus: Id,
+}
+
+impl PeriodicFreshRange {
+    pub fn new(lower_id: Id, upper_id: Id, modulus: Id) -> Self {
+        assert!(modulus > 0, "Modulus must be greater than zero");
+
+        Self {
+            lower_id,
+            upper_id,
+            modulus,
+        }
+    }
+
+    pub fn is_id_fresh(&self, id: &Id) -> bool {
+        let id = id % self.modulus;
+        let lower_id = self.lower_id % self.modulus;
+        let upper_id = self.upper_id % self.modulus;
+
+        match lower_id <= upper_id {
+            true => id >= lower_id && id <= upper_id,
+            // Wraps around the end of the cycle
+            false => id >= lower_id || id <= upper_id,
+        }
+    }
+
+    /// Fresh IDs within a single cycle, from 0 up to the modulus
+    pub fn get_fresh_ids(&self) -> impl Iterator<Item = Id> {
+        (0..self.modulus).filter(|id| self.is_id_fresh(id))
+    }
+}
+
+/// Segment tree over the ranges sorted by lower ID, where every node keeps the greatest upper ID
+/// below it
+pub struct RangeSegmentTree {
+    lower_ids: Vec<Id>,
+    max_upper_ids: Vec<Id>,
+}
+
+impl RangeSegmentTree {
+    pub fn new(ranges: &[FreshRange]) -> Self {
+        let mut ranges = ranges.to_vec();
+        ranges.sort_by_key(|range| range.lower_id);
+
+        let ranges_count = ranges.len();
+
+        // Leaves are stored after the inner nodes, the root being at index 1
+        let mut max_upper_ids = vec![0; 2 * ranges_count];
+
+        for (index, range) in ranges.iter().enumerate() {
+            max_upper_ids[ranges_count + index] = range.upper_id;
+        }
+
+        for index in (1..ranges_count).rev() {
+            max_upper_ids[index] = max_upper_ids[2 * index].max(max_upper_ids[2 * index + 1]);
+        }
+
+        Self {
+            lower_ids: ranges.iter().map(|range| range.lower_id).collect(),
+            max_upper_ids,
+        }
+    }
+
+    /// Only the ranges starting at or before the ID can contain it, so it's enough to check
+    /// whether any of them ends at or after it
+    pub fn contains(&self, id: Id) -> bool {
+        let candidates_count = self.lower_ids.partition_point(|&lower_id| lower_id <= id);
+
+        self.max_upper_id(candidates_count)
+            .is_some_and(|max_upper_id| max_upper_id >= id)
+    }
+
+    /// Greatest upper ID among the first `count` ranges
+    fn max_upper_id(&self, count: usize) -> Option<Id> {
+        let ranges_count = self.lower_ids.len();
+        let mut max_upper_id = None;
+
+        let (mut left, mut right) = (ranges_count, ranges_count + count);
+
+        while left < right {
+            if left % 2 == 1 {
+                max_upper_id = max_upper_id.max(Some(self.max_upper_ids[left]));
+                left += 1;
+            }
+
+            if right % 2 == 1 {
+                right -= 1;
+                max_upper_id = max_upper_id.max(Some(self.max_upper_ids[right]));
+            }
+
+            left /= 2;
+            right /= 2;
+        }
+
+        max_upper_id
+    }
+}
+
+pub struct KitchenDB {
+    fresh_id_ranges: Vec<FreshRange>,
+    ingredients_ids: Vec<Id>,
+    fresh_ranges_index: Option<RangeSegmentTree>,
+}
+
+impl KitchenDB {
+    const CSV_HEADER: &str = "type,value";
+
+    pub fn get_fresh_available_ids(&self) -> impl Iterator<Item = &Id> {
+        self.ingredients_ids.iter().filter(|ingredient_id| {
+            self.fresh_id_ranges
+                .iter()
+                .any(|range| range.is_id_fresh(ingredient_id))
+        })
+    }
+
+    /// Largest fresh ingredient, in a single pass over the ingredients
+    pub fn max_fresh_ingredient(&self) -> Option<Id> {
+        self.get_fresh_available_ids().max().copied()
+    }
+
+    /// Smallest fresh ingredient, in a single pass over the ingredients
+    pub fn min_fresh_ingredient(&self) -> Option<Id> {
+        self.get_fresh_available_ids().min().copied()
+    }
+
+    /// Add the ranges and ingredients of another database, optimizing the combined ranges
+    pub fn merge(&mut self, other: KitchenDB) {
+        self.fresh_id_ranges.extend(other.fresh_id_ranges);
+        self.ingredients_ids.extend(other.ingredients_ids);
+
+        self.optimize();
+    }
+
+    /// Merge the overlapping ranges in place, keeping the same fresh IDs. Adjacent ranges like
+    /// 3-5 and 6-8 stay apart
+    pub fn optimize(&mut self) {
+        self.fresh_id_ranges = KitchenDBUtils::optimize_ranges(&self.fresh_id_ranges);
+
+        // Keep an existing index in sync with the new ranges
+        if self.fresh_ranges_index.is_some() {
+            self.build_index();
+        }
+    }
+
+    pub fn has_duplicate_ingredients(&self) -> bool {
+        let mut seen_ids = HashSet::with_capacity(self.ingredients_ids.len());
+
+        !self.ingredients_ids.iter().all(|id| seen_ids.insert(id))
+    }
+
+    /// Sort the ingredients and drop the repeated ones, so each one is only counted once
+    pub fn deduplicate_ingredients(&mut self) {
+        self.ingredients_ids.sort_unstable();
+        self.ingredients_ids.dedup();
+    }
+
+    /// Build the ranges index used by `get_fresh_available_ids_indexed`
+    pub fn build_index(&mut self) {
+        self.fresh_ranges_index = Some(KitchenDBUtils::segment_tree(&self.fresh_id_ranges));
+    }
+
+    /// Same as `get_fresh_available_ids`, in O(log n) per ingredient
+    pub fn get_fresh_available_ids_indexed(&self) -> impl Iterator<Item = &Id> {
+        let fresh_ranges_index = self
+            .fresh_ranges_index
+            .as_ref()
+            .expect("Index not built, call build_index first");
+
+        self.ingredients_ids
+            .iter()
+            .filter(|&&ingredient_id| fresh_ranges_index.contains(ingredient_id))
+    }
+
+    pub fn get_fresh_ids_count(&self) -> u128 {
+        // Inverted ranges hold no IDs, but merging them would shrink the ranges they touch
+        let ranges: Vec<FreshRange> = self
+            .fresh_id_ranges
+            .iter()
+            .filter(|range| range.size() > 0)
+            .cloned()
+            .collect();
+
+        KitchenDBUtils::optimize_ranges(&ranges)
+            .iter()
+            .map(|range| range.size())
+            .sum()
+    }
+
+    pub fn stale_ingredients_beyond_ranges(&self) -> impl Iterator<Item = &Id> {
+        let max_upper_id = self
+            .fresh_id_ranges
+            .iter()
+            .map(|range| range.upper_id)
+            .max();
+
+        // Without any range every ingredient is stale
+        self.ingredients_ids
+            .iter()
+            .filter(move |&&id| max_upper_id.is_none_or(|max_upper_id| id > max_upper_id))
+    }
+
+    pub fn stale_ingredients_below_ranges(&self) -> impl Iterator<Item = &Id> {
+        let min_lower_id = self
+            .fresh_id_ranges
+            .iter()
+            .map(|range| range.lower_id)
+            .min();
+
+        // Without any range every ingredient is stale
+        self.ingredients_ids
+            .iter()
+            .filter(move |&&id| min_lower_id.is_none_or(|min_lower_id| id < min_lower_id))
+    }
+
+    /// Ingredients below or beyond every range, each of them once. Without any range every
+    /// ingredient is stale
+    pub fn stale_ingredients_outside_ranges(&self) -> impl Iterator<Item = &Id> {
+        let bounds = self
+            .fresh_id_ranges
+            .iter()
+            .map(|range| (range.lower_id, range.upper_id))
+            .reduce(|(min_lower_id, max_upper_id), (lower_id, upper_id)| {
+                (min_lower_id.min(lower_id), max_upper_id.max(upper_id))
+            });
+
+        self.ingredients_ids.iter().filter(move |&&id| {
+            bounds.is_none_or(|(min_lower_id, max_upper_id)| id < min_lower_id || id > max_upper_id)
+        })
+    }
+
+    /// Parse rows like `range,3-5` or `ingredient,11` after a `type,value` header
+    pub fn from_csv(value: &str) -> Result<KitchenDB, ParseError> {
+        let mut lines = value
+            .lines()
+            .map(|line| line.trim())
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !line.is_empty());
+
+        match lines.next() {
+            Some((_, header)) if header == Self::CSV_HEADER => {}
+            _ => return Err(ParseError::MissingHeader),
+        }
+
+        let mut fresh_id_ranges = Vec::new();
+        let mut ingredients_ids = Vec::new();
+
+        for (line_number, line) in lines {
+            let (row_type, row_value) = line
+                .split_once(',')
+                .ok_or(ParseError::MissingColumn { line_number })?;
+
+            let row_value = row_value.trim();
+
+            let invalid_number = || ParseError::InvalidNumber {
+                line_number,
+                value: row_value.to_string(),
+            };
+
+            match row_type.trim() {
+                "range" => {
+                    let (lower_id, upper_id) =
+                        row_value.split_once('-').ok_or_else(invalid_number)?;
+
+                    fresh_id_ranges.push(FreshRange {
+                        lower_id: lower_id.trim().parse().map_err(|_| invalid_number())?,
+                        upper_id: upper_id.trim().parse().map_err(|_| invalid_number())?,
+                    });
+                }
+                "ingredient" => {
+                    ingredients_ids.push(row_value.parse().map_err(|_| invalid_number())?);
+                }
+                row_type => {
+                    return Err(ParseError::UnknownType {
+                        line_number,
+                        value: row_type.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            fresh_id_ranges,
+            ingredients_ids,
+            fresh_ranges_index: None,
+        })
+    }
+
+    pub fn to_csv(&self) -> String {
+        let ranges_rows = self
+            .fresh_id_ranges
+            .iter()
+            .map(|range| format!("range,{}-{}", range.lower_id, range.upper_id));
+
+        let ingredients_rows = self
+            .ingredients_ids
+            .iter()
+            .map(|id| format!("ingredient,{}", id));
+
+        [Self::CSV_HEADER.to_string()]
+            .into_iter()
+            .chain(ranges_rows)
+            .chain(ingredients_rows)
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl From<&str> for KitchenDB {
+    fn from(value: &str) -> Self {
+        let mut is_ranges_completed = false;
+        let mut fresh_id_ranges = Vec::new();
+        let mut ingredients_ids = Vec::new();
+
+        for line in value.trim().lines() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                is_ranges_completed = true;
+                continue;
+            }
+
+            match is_ranges_completed {
+                true => {
+                    let id = line.parse().expect("Invalid number");
+                    ingredients_ids.push(id);
+                }
+                false => {
+                    let range = FreshRange::from(line);
+                    fresh_id_ranges.push(range);
+                }
+            }
+        }
+
+        Self {
+            fresh_id_ranges,
+            ingredients_ids,
+            fresh_ranges_index: None,
+        }
+    }
+}
+
+pub struct KitchenDBUtils {}
+
+impl KitchenDBUtils {
+    pub fn optimize_ranges(ranges: &[FreshRange]) -> Vec<FreshRange> {
+        let mut ranges = VecDeque::from(ranges.to_owned());
+
+        for _ in 0..ranges.len() {
+            let current_range = ranges.pop_front().unwrap();
+            let mut is_current_range_merged = false;
+
+            for other_range in ranges.iter_mut() {
+                let merged_range = Self::merge_ranges(&current_range, other_range);
+
+                if let Some(new_range) = merged_range {
+                    *other_range = new_range;
+                    is_current_range_merged = true;
+                    break;
+                }
+            }
+
+            if !is_current_range_merged {
+                ranges.push_back(current_range);
+            }
+        }
+
+        Vec::from(ranges)
+    }
+
+    pub fn segment_tree(ranges: &[FreshRange]) -> RangeSegmentTree {
+        RangeSegmentTree::new(ranges)
+    }
+
+    /// Number of unordered range pairs overlapping each other
+    pub fn ranges_overlap_count(ranges: &[FreshRange]) -> usize {
+        ranges
+            .iter()
+            .enumerate()
+            .map(|(index, range)| {
+                ranges[index + 1..]
+                    .iter()
+                    .filter(|other_range| range.overlap_with(other_range))
+                    .count()
+            })
+            .sum()
+    }
+
+    fn merge_ranges(source_range: &FreshRange, target_range: &FreshRange) -> Option<FreshRange> {
+        let is_lower_within = (source_range.lower_id >= target_range.lower_id)
+            && (source_range.lower_id <= target_range.upper_id);
+
+        let is_upper_within = (source_range.upper_id >= target_range.lower_id)
+            && (source_range.upper_id <= target_range.upper_id);
+
+        match (is_lower_within, is_upper_within) {
+            (true, true) => Some(target_range.clone()),
+            (true, false) => Some(FreshRange {
+                lower_id: target_range.lower_id,
+                upper_id: source_range.upper_id,
+            }),
+            (false, true) => Some(FreshRange {
+                lower_id: source_range.lower_id,
+                upper_id: target_range.upper_id,
+            }),
+            (false, false) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_input() {
+        let data = r#"
+            3-5
+            10-14
+            16-20
+            12-18
+
+            1
+            5
+            8
+            11
+            17
+            32"#;
+
+        let kitchen_db = KitchenDB::from(data);
+
+        assert_eq!(kitchen_db.get_fresh_available_ids().count(), 3);
+
+        assert_eq!(kitchen_db.get_fresh_ids_count(), 14);
+    }
+
+    #[test]
+    fn test_fresh_range_ids() {
+        let fresh_range = FreshRange::from("3-5");
+
+        let mut fresh_ids = fresh_range.get_fresh_ids();
+
+        assert_eq!(fresh_ids.len(), 3);
+        assert_eq!(fresh_ids.len(), 3);
+        assert_eq!(fresh_ids.next(), Some(3));
+        assert_eq!(fresh_ids.len(), 2);
+        assert_eq!(fresh_ids.collect::<Vec<Id>>(), Vec::from([4, 5]));
+
+        assert_eq!(
+            FreshRange::from("7-7").get_fresh_ids().len(),
+            FreshRange::from("7-7").size()
+        );
+
+        // Too many IDs for a `usize`, still counted exactly
+        let whole_range = FreshRange {
+            lower_id: 0,
+            upper_id: Id::MAX,
+        };
+
+        assert_eq!(whole_range.get_fresh_ids().len(), Id::MAX as u128 + 1);
+        assert!(FreshRange::from("1-0").get_fresh_ids().is_empty());
+        assert_eq!(FreshRange::from("1-0").get_fresh_ids().len(), 0);
+    }
+
+    #[test]
+    fn test_fresh_range_as_inclusive_range() {
+        fn total_size(ranges: &[impl InclusiveRange]) -> u128 {
+            ranges.iter().map(|range| range.size()).sum()
+        }
+
+        let fresh_range = FreshRange::from("3-5");
+
+        assert_eq!((fresh_range.lower(), fresh_range.upper()), (3, 5));
+        assert!(fresh_range.contains(3) && fresh_range.contains(5));
+        assert!(!fresh_range.contains(6));
+        assert_eq!(
+            fresh_range.iter_ids().collect::<Vec<Id>>(),
+            fresh_range.get_fresh_ids().collect::<Vec<Id>>()
+        );
+        assert_eq!(
+            total_size(&[FreshRange::from("3-5"), FreshRange::from("10-14")]),
+            8
+        );
+
+        let whole_range = FreshRange {
+            lower_id: 0,
+            upper_id: Id::MAX,
+        };
+
+        assert_eq!(whole_range.size(), Id::MAX as u128 + 1);
+    }
+
+    #[test]
+    fn test_fresh_range_shift() {
+        let range = FreshRange::from("10-20");
+
+        assert_eq!(range.checked_shift(5), Some(FreshRange::from("15-25")));
+        assert_eq!(range.checked_shift(-10), Some(FreshRange::from("0-10")));
+        assert_eq!(range.saturating_shift(-3), FreshRange::from("7-17"));
+
+        // Middle of the ID space
+        let half_id = Id::MAX / 2;
+        let range = FreshRange {
+            lower_id: half_id,
+            upper_id: half_id + 10,
+        };
+        let shifted = FreshRange {
+            lower_id: half_id - 1_000,
+            upper_id: half_id - 990,
+        };
+
+        assert_eq!(range.checked_shift(-1_000), Some(shifted.clone()));
+        assert_eq!(range.saturating_shift(-1_000), shifted);
+        // The lower bound still fits, but not the upper one
+        assert_eq!(range.checked_shift(i64::MAX), None);
+
+        // Positive delta near the top of the ID space
+        let range = FreshRange {
+            lower_id: Id::MAX - 10,
+            upper_id: Id::MAX - 2,
+        };
+
+        assert_eq!(
+            range.checked_shift(2),
+            Some(FreshRange {
+                lower_id: Id::MAX - 8,
+                upper_id: Id::MAX,
+            })
+        );
+        assert_eq!(range.checked_shift(3), None);
+        assert_eq!(
+            range.saturating_shift(5),
+            FreshRange {
+                lower_id: Id::MAX - 5,
+                upper_id: Id::MAX,
+            }
+        );
+        assert_eq!(
+            range.saturating_shift(i64::MAX),
+            FreshRange {
+                lower_id: Id::MAX,
+                upper_id: Id::MAX,
+            }
+        );
+
+        // Negative delta near zero
+        let range = FreshRange::from("3-8");
+
+        assert_eq!(range.checked_shift(-4), None);
+        assert_eq!(range.saturating_shift(-4), FreshRange::from("0-4"));
+        assert_eq!(range.saturating_shift(i64::MIN), FreshRange::from("0-0"));
+    }
+
+    #[test]
+    fn test_fresh_range_midpoint() {
+        assert_eq!(FreshRange::from("10-12").midpoint(), Some(11));
+        assert_eq!(FreshRange::from("10-13").midpoint(), Some(11));
+        assert_eq!(FreshRange::from("7-7").midpoint(), Some(7));
+        assert_eq!(FreshRange::from("0-1").midpoint(), Some(0));
+        assert_eq!(FreshRange::from("5-3").midpoint(), None);
+
+        let half_id = Id::MAX / 2;
+
+        assert_eq!(
+            FreshRange {
+                lower_id: half_id,
+                upper_id: half_id + 2,
+            }
+            .midpoint(),
+            Some(half_id + 1)
+        );
+        assert_eq!(
+            FreshRange {
+                lower_id: half_id + 1,
+                upper_id: Id::MAX,
+            }
+            .midpoint(),
+            Some(half_id + 1 + half_id / 2)
+        );
+        assert_eq!(
+            FreshRange {
+                lower_id: 0,
+                upper_id: Id::MAX,
+            }
+            .midpoint(),
+            Some(half_id)
+        );
+
+        let fresh_range = FreshRange::from("10-20");
+
+        assert!(fresh_range.contains_midpoint_of(&FreshRange::from("5-25")));
+        assert!(fresh_range.contains_midpoint_of(&FreshRange::from("20-20")));
+        assert!(!fresh_range.contains_midpoint_of(&FreshRange::from("0-18")));
+        // Not symmetric, 15 is in 0-18 but 9 isn't in 10-20
+        assert!(FreshRange::from("0-18").contains_midpoint_of(&fresh_range));
+        assert!(!fresh_range.contains_midpoint_of(&FreshRange::from("20-10")));
+    }
+
+    #[test]
+    fn test_fresh_ids_count_large_ranges() {
+        let kitchen_db = KitchenDB::from("0-4000000000\n3000000000-5000000000\n\n1");
+
+        assert_eq!(kitchen_db.get_fresh_ids_count(), 5_000_000_001);
+
+        let kitchen_db = KitchenDB::from(format!("0-{}\n\n1", Id::MAX).as_str());
+
+        assert_eq!(kitchen_db.get_fresh_ids_count(), Id::MAX as u128 + 1);
+
+        // Inverted ranges are empty, and leave the ranges around them whole
+        assert_eq!(KitchenDB::from("5-3\n\n4").get_fresh_ids_count(), 0);
+        assert_eq!(KitchenDB::from("5-3\n1-4\n\n4").get_fresh_ids_count(), 4);
+    }
+
+    #[test]
+    fn test_indexed_fresh_ids() {
+        let data = r#"
+            3-5
+            10-14
+            16-20
+            12-18
+
+            1
+            5
+            8
+            11
+            17
+            32"#;
+
+        let mut kitchen_db = KitchenDB::from(data);
+        kitchen_db.build_index();
+
+        let fresh_ids: Vec<&Id> = kitchen_db.get_fresh_available_ids().collect();
+
+        assert_eq!(fresh_ids, Vec::from([&5, &11, &17]));
+        assert_eq!(
+            kitchen_db
+                .get_fresh_available_ids_indexed()
+                .collect::<Vec<&Id>>(),
+            fresh_ids
+        );
+
+        // Nested and disjoint ranges, queried on every ID around them
+        let ranges = Vec::from([
+            FreshRange {
+                lower_id: 0,
+                upper_id: 0,
+            },
+            FreshRange {
+                lower_id: 40,
+                upper_id: 45,
+            },
+            FreshRange {
+                lower_id: 10,
+                upper_id: 30,
+            },
+            FreshRange {
+                lower_id: 12,
+                upper_id: 14,
+            },
+            FreshRange {
+                lower_id: 31,
+                upper_id: 31,
+            },
+        ]);
+
+        let mut kitchen_db = KitchenDB {
+            fresh_id_ranges: ranges,
+            ingredients_ids: (0..50).collect(),
+            fresh_ranges_index: None,
+        };
+        kitchen_db.build_index();
+
+        assert_eq!(
+            kitchen_db
+                .get_fresh_available_ids_indexed()
+                .collect::<Vec<&Id>>(),
+            kitchen_db.get_fresh_available_ids().collect::<Vec<&Id>>()
+        );
+        assert_eq!(kitchen_db.get_fresh_available_ids_indexed().count(), 29);
+
+        assert!(!KitchenDBUtils::segment_tree(&[]).contains(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index not built")]
+    fn test_indexed_fresh_ids_without_index() {
+        let kitchen_db = KitchenDB::from("3-5\n\n4");
+
+        let _ = kitchen_db.get_fresh_available_ids_indexed().count();
+    }
+
+    #[test]
+    fn test_merge_kitchen_dbs() {
+        let mut kitchen_db = KitchenDB::from(
+            r#"
+            3-5
+            10-14
+
+            1
+            4
+            12
+            17"#,
+        );
+        kitchen_db.build_index();
+
+        let other_kitchen_db = KitchenDB::from(
+            r#"
+            16-20
+            12-18
+
+            1
+            19"#,
+        );
+
+        kitchen_db.merge(other_kitchen_db);
+
+        assert_eq!(
+            kitchen_db.fresh_id_ranges,
+            Vec::from([
+                FreshRange {
+                    lower_id: 3,
+                    upper_id: 5
+                },
+                FreshRange {
+                    lower_id: 10,
+                    upper_id: 20
+                }
+            ])
+        );
+        assert_eq!(kitchen_db.ingredients_ids, Vec::from([1, 4, 12, 17, 1, 19]));
+
+        // 17 was only fresh in the other database, and 4 only in this one
+        let fresh_ids: Vec<&Id> = kitchen_db.get_fresh_available_ids().collect();
+
+        assert_eq!(fresh_ids, Vec::from([&4, &12, &17, &19]));
+        assert_eq!(
+            kitchen_db
+                .get_fresh_available_ids_indexed()
+                .collect::<Vec<&Id>>(),
+            fresh_ids
+        );
+        assert_eq!(kitchen_db.get_fresh_ids_count(), 14);
+    }
+
+    #[test]
+    fn test_deduplicate_ingredients() {
+        let mut kitchen_db = KitchenDB::from(
+            r#"
+            3-5
+            10-14
+
+            12
+            4
+            17
+            4
+            12
+            12"#,
+        );
+
+        assert!(kitchen_db.has_duplicate_ingredients());
+        assert_eq!(kitchen_db.get_fresh_available_ids().count(), 5);
+
+        kitchen_db.deduplicate_ingredients();
+
+        assert!(!kitchen_db.has_duplicate_ingredients());
+        assert_eq!(kitchen_db.ingredients_ids, Vec::from([4, 12, 17]));
+        assert_eq!(
+            kitchen_db.get_fresh_available_ids().collect::<Vec<&Id>>(),
+            Vec::from([&4, &12])
+        );
+        assert_eq!(kitchen_db.get_fresh_ids_count(), 8);
+
+        kitchen_db.build_index();
+        assert_eq!(kitchen_db.get_fresh_available_ids_indexed().count(), 2);
+    }
+
+    #[test]
+    fn test_stale_ingredients_outside_ranges() {
+        let data = r#"
+            3-5
+            10-14
+            16-20
+            12-18
+
+            1
+            5
+            8
+            11
+            17
+            32"#;
+
+        let kitchen_db = KitchenDB::from(data);
+
+        let beyond_ids: Vec<&Id> = kitchen_db.stale_ingredients_beyond_ranges().collect();
+        assert_eq!(beyond_ids, Vec::from([&32]));
+
+        let below_ids: Vec<&Id> = kitchen_db.stale_ingredients_below_ranges().collect();
+        assert_eq!(below_ids, Vec::from([&1]));
+
+        let outside_ids: Vec<&Id> = kitchen_db.stale_ingredients_outside_ranges().collect();
+        assert_eq!(outside_ids, Vec::from([&1, &32]));
+
+        let kitchen_db = KitchenDB {
+            fresh_id_ranges: Vec::new(),
+            ingredients_ids: Vec::from([1, 2]),
+            fresh_ranges_index: None,
+        };
+
+        assert_eq!(kitchen_db.stale_ingredients_beyond_ranges().count(), 2);
+        assert_eq!(kitchen_db.stale_ingredients_below_ranges().count(), 2);
+        // Counted once, even though they're both below and beyond the missing ranges
+        assert_eq!(kitchen_db.stale_ingredients_outside_ranges().count(), 2);
+    }
+
+    #[test]
+    fn test_min_max_fresh_ingredient() {
+        let data = r#"
+            3-5
+            10-14
+            16-20
+            12-18
+
+            1
+            5
+            8
+            11
+            17
+            32"#;
+
+        let kitchen_db = KitchenDB::from(data);
+
+        assert_eq!(kitchen_db.min_fresh_ingredient(), Some(5));
+        assert_eq!(kitchen_db.max_fresh_ingredient(), Some(17));
+
+        let kitchen_db = KitchenDB::from("3-5\n\n1\n8\n2");
+
+        assert_eq!(kitchen_db.min_fresh_ingredient(), None);
+        assert_eq!(kitchen_db.max_fresh_ingredient(), None);
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let data = "type,value\nrange,3-5\nrange,10-14\ningredient,11\ningredient,4";
+
+        let kitchen_db = KitchenDB::from_csv(data).unwrap();
+
+        assert_eq!(
+            kitchen_db.fresh_id_ranges,
+            Vec::from([
+                FreshRange {
+                    lower_id: 3,
+                    upper_id: 5
+                },
+                FreshRange {
+                    lower_id: 10,
+                    upper_id: 14
+                }
+            ])
+        );
+        assert_eq!(kitchen_db.ingredients_ids, Vec::from([11, 4]));
+        assert_eq!(kitchen_db.get_fresh_available_ids().count(), 2);
+
+        assert_eq!(kitchen_db.to_csv(), data);
+
+        let kitchen_db = KitchenDB::from(
+            r#"
+            3-5
+            10-14
+
+            1
+            5"#,
+        );
+        let parsed_kitchen_db = KitchenDB::from_csv(&kitchen_db.to_csv()).unwrap();
+
+        assert_eq!(
+            parsed_kitchen_db.fresh_id_ranges,
+            kitchen_db.fresh_id_ranges
+        );
+        assert_eq!(
+            parsed_kitchen_db.ingredients_ids,
+            kitchen_db.ingredients_ids
+        );
+    }
+
+    #[test]
+    fn test_csv_errors() {
+        assert_eq!(
+            KitchenDB::from_csv("range,3-5").err(),
+            Some(ParseError::MissingHeader)
+        );
+
+        assert_eq!(
+            KitchenDB::from_csv("type,value\nrange,3-5\ningredient").err(),
+            Some(ParseError::MissingColumn { line_number: 3 })
+        );
+
+        assert_eq!(
+            KitchenDB::from_csv("type,value\nspice,3").err(),
+            Some(ParseError::UnknownType {
+                line_number: 2,
+                value: String::from("spice")
+            })
+        );
+
+        assert_eq!(
+            KitchenDB::from_csv("type,value\ningredient,abc").err(),
+            Some(ParseError::InvalidNumber {
+                line_number: 2,
+                value: String::from("abc")
+            })
+        );
+
+        assert_eq!(
+            KitchenDB::from_csv("type,value\nrange,3").err(),
+            Some(ParseError::InvalidNumber {
+                line_number: 2,
+                value: String::from("3")
+            })
+        );
+    }
+
+    #[test]
+    fn test_periodic_fresh_range() {
+        let clock_range = PeriodicFreshRange::new(22, 4, 24);
+
+        for id in [22, 23, 0, 1, 2, 3, 4] {
+            assert!(clock_range.is_id_fresh(&id));
+        }
+
+        for id in [5, 12, 21] {
+            assert!(!clock_range.is_id_fresh(&id));
+        }
+
+        // Compared modulo 24
+        assert!(clock_range.is_id_fresh(&25));
+        assert!(!clock_range.is_id_fresh(&29));
+
+        let fresh_ids: Vec<Id> = clock_range.get_fresh_ids().collect();
+        assert_eq!(fresh_ids, Vec::from([0, 1, 2, 3, 4, 22, 23]));
+
+        let day_range = PeriodicFreshRange::new(1, 5, 7);
+        assert_eq!(day_range.get_fresh_ids().count(), 5);
+        assert!(!day_range.is_id_fresh(&7));
+    }
+
+    #[test]
+    fn test_optimize_ranges() {
+        let ranges = Vec::from([
+            FreshRange {
+                lower_id: 12,
+                upper_id: 18,
+            },
+            FreshRange {
+                lower_id: 16,
+                upper_id: 20,
+            },
+        ]);
+
+        let ranges = KitchenDBUtils::optimize_ranges(&ranges);
+
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].lower_id, 12);
+        assert_eq!(ranges[0].upper_id, 20);
+    }
+
+    #[test]
+    fn test_optimize_kitchen_db() {
+        let data = r#"
+            3-5
+            10-14
+            16-20
+            12-18
+
+            1
+            5
+            8
+            11
+            17
+            32"#;
+
+        let mut kitchen_db = KitchenDB::from(data);
+        kitchen_db.fresh_id_ranges.push(FreshRange {
+            lower_id: 4,
+            upper_id: 8,
+        });
+        kitchen_db.build_index();
+
+        let fresh_ids_count = kitchen_db.get_fresh_ids_count();
+        let fresh_ids: Vec<Id> = kitchen_db.get_fresh_available_ids().copied().collect();
+
+        assert_eq!(kitchen_db.fresh_id_ranges.len(), 5);
+        assert_eq!(fresh_ids, Vec::from([5, 8, 11, 17]));
+        assert_eq!(fresh_ids_count, 17);
+
+        kitchen_db.optimize();
+        kitchen_db
+            .fresh_id_ranges
+            .sort_by_key(|range| range.lower_id);
+
+        assert_eq!(
+            kitchen_db.fresh_id_ranges,
+            Vec::from([
+                FreshRange {
+                    lower_id: 3,
+                    upper_id: 8
+                },
+                FreshRange {
+                    lower_id: 10,
+                    upper_id: 20
+                }
+            ])
+        );
+        assert_eq!(kitchen_db.get_fresh_ids_count(), fresh_ids_count);
+        assert_eq!(
+            kitchen_db
+                .get_fresh_available_ids()
+                .copied()
+                .collect::<Vec<Id>>(),
+            fresh_ids
+        );
+        assert_eq!(
+            kitchen_db
+                .get_fresh_available_ids_indexed()
+                .copied()
+                .collect::<Vec<Id>>(),
+            fresh_ids
+        );
+
+        // Adjacent ranges don't overlap, so they aren't merged
+        let mut kitchen_db = KitchenDB::from("3-5\n6-8\n\n1");
+        kitchen_db.optimize();
+
+        assert_eq!(kitchen_db.fresh_id_ranges.len(), 2);
+        assert_eq!(kitchen_db.get_fresh_ids_count(), 6);
+    }
+
+    #[test]
+    fn test_ranges_overlap_count() {
+        let data = r#"
+            3-5
+            10-14
+            16-20
+            12-18
+
+            1"#;
+
+        let kitchen_db = KitchenDB::from(data);
+
+        // 10-14 with 12-18, and 16-20 with 12-18
+        assert_eq!(
+            KitchenDBUtils::ranges_overlap_count(&kitchen_db.fresh_id_ranges),
+            2
+        );
+
+        let ranges = Vec::from([
+            FreshRange {
+                lower_id: 1,
+                upper_id: 2,
+            },
+            FreshRange {
+                lower_id: 3,
+                upper_id: 4,
+            },
+            FreshRange {
+                lower_id: 10,
+                upper_id: 20,
+            },
+        ]);
+
+        assert_eq!(KitchenDBUtils::ranges_overlap_count(&ranges), 0);
+    }
+}
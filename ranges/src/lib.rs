@@ -0,0 +1,70 @@
+//! Inclusive ID ranges shared by the puzzles working with ID intervals.
+
+pub type Id = u64;
+
+/// Range of IDs including both bounds. A lower bound above the upper one makes it empty
+pub trait InclusiveRange {
+    fn lower(&self) -> Id;
+
+    fn upper(&self) -> Id;
+
+    fn contains(&self, id: Id) -> bool {
+        self.lower() <= id && id <= self.upper()
+    }
+
+    /// Number of IDs in the range, wide enough for the whole ID space
+    fn size(&self) -> u128 {
+        (self.upper() as u128 + 1).saturating_sub(self.lower() as u128)
+    }
+
+    /// Every ID in the range, in increasing order
+    fn iter_ids(&self) -> impl Iterator<Item = Id> {
+        self.lower()..=self.upper()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestRange(Id, Id);
+
+    impl InclusiveRange for TestRange {
+        fn lower(&self) -> Id {
+            self.0
+        }
+
+        fn upper(&self) -> Id {
+            self.1
+        }
+    }
+
+    #[test]
+    fn default_methods() {
+        let range = TestRange(3, 7);
+
+        assert!(range.contains(3));
+        assert!(range.contains(7));
+        assert!(!range.contains(2));
+        assert!(!range.contains(8));
+        assert_eq!(range.size(), 5);
+        assert_eq!(
+            range.iter_ids().collect::<Vec<Id>>(),
+            Vec::from([3, 4, 5, 6, 7])
+        );
+
+        let single_id = TestRange(9, 9);
+
+        assert_eq!(single_id.size(), 1);
+        assert_eq!(single_id.iter_ids().collect::<Vec<Id>>(), Vec::from([9]));
+
+        assert_eq!(TestRange(0, Id::MAX).size(), Id::MAX as u128 + 1);
+
+        let inverted = TestRange(5, 3);
+
+        assert_eq!(inverted.size(), 0);
+        assert!(!inverted.contains(4));
+        assert_eq!(inverted.iter_ids().count(), 0);
+        assert!(TestRange(0, Id::MAX).contains(Id::MAX));
+    }
+}